@@ -4,88 +4,136 @@ use tutorial::*;
 mod shared;
 mod tutorial;
 
+use std::env;
+use std::process::{self, Command};
+
+/// number of frames rendered by `--headless` when no count is given
+const DEFAULT_HEADLESS_FRAMES: u32 = 3;
+
+const USAGE: &str = "\
+Usage: gfx-rs [--headless [frames] [--capture <png>]] <command>
+
+Commands:
+    list                      list all tutorials of the enabled chapters
+    run <id|title>            run a tutorial, e.g. `run 1_2_1`, `run 1.2.1` or `run hello triangle`
+    run-chapter <n>           run every tutorial of chapter n, one after the other
+    golden [--bless] [ids]    compare tutorials (default: all) against their reference images
+    gl-info                   print the renderer and version of the headless GL context
+    <id|title>                same as `run`
+
+Options:
+    --headless [frames]       render the given number of frames (default 3) offscreen and exit
+    --capture <png>           save the last headless frame";
+
 fn main() {
-    let args: Vec<String> = std::env::args().collect();
-    if args.len() != 2 {
-        println!("Call with the number of the tutorial, e.g. `1_1_2` for _1_2_hello_window_clear.rs");
-        std::process::exit(1);
+    let mut args: Vec<String> = env::args().skip(1).collect();
+
+    // headless options are handed on to the tutorials started by `run-chapter`
+    let mut headless_args = Vec::new();
+    if args.first().map(String::as_str) == Some("--headless") {
+        headless_args.push(args.remove(0));
+        let frames = match args.first().map(|frames| frames.parse::<u32>()) {
+            Some(Ok(frames)) if args.len() > 1 => {
+                headless_args.push(args.remove(0));
+                frames
+            }
+            _ => DEFAULT_HEADLESS_FRAMES,
+        };
+        set_headless(frames);
+        if args.first().map(String::as_str) == Some("--capture") && args.len() > 2 {
+            set_capture_path(args.remove(1));
+            args.remove(0);
+        }
+    }
+
+    if args.is_empty() {
+        exit_with_usage();
+    }
+    let command = args.remove(0);
+    match command.as_str() {
+        "list" if args.is_empty() => list_tutorials(),
+        "run" if !args.is_empty() => run_tutorial(&args.join(" ")),
+        "run-chapter" if args.len() == 1 => run_chapter(&args[0], &headless_args),
+        "golden" => {
+            let bless = args.first().map(String::as_str) == Some("--bless");
+            if bless {
+                args.remove(0);
+            }
+            if args.is_empty() {
+                args = tutorials().iter().map(|t| t.id.to_string()).collect();
+            }
+            let passed = run_golden_tests(&args, bless);
+            process::exit(if passed { 0 } else { 1 });
+        }
+        "gl-info" if args.is_empty() => println!("{}", gl_renderer()),
+        query if args.is_empty() => run_tutorial(query),
+        _ => exit_with_usage(),
     }
-    let tutorial_id = &args[1];
+}
 
-    match tutorial_id.as_str() {
-        #[cfg(feature = "chapter-1")]
-        "1_1_1" => main_1_1_1(),
-        #[cfg(feature = "chapter-1")]
-        "1_1_2" => main_1_1_2(),
-        #[cfg(feature = "chapter-1")]
-        "1_2_1" => main_1_2_1(),
-        #[cfg(feature = "chapter-1")]
-        "1_2_2" => main_1_2_2(),
-        #[cfg(feature = "chapter-1")]
-        "1_2_3" => main_1_2_3(),
-        #[cfg(feature = "chapter-1")]
-        "1_2_4" => main_1_2_4(),
-        #[cfg(feature = "chapter-1")]
-        "1_2_5" => main_1_2_5(),
-        #[cfg(feature = "chapter-1")]
-        "1_3_1" => main_1_3_1(),
-        #[cfg(feature = "chapter-1")]
-        "1_3_2" => main_1_3_2(),
-        #[cfg(feature = "chapter-1")]
-        "1_3_3" => main_1_3_3(),
-        #[cfg(feature = "chapter-1")]
-        "1_4_1" => main_1_4_1(),
-        #[cfg(feature = "chapter-1")]
-        "1_4_2" => main_1_4_2(),
-        #[cfg(feature = "chapter-1")]
-        "1_5_1" => main_1_5_1(),
-        #[cfg(feature = "chapter-1")]
-        "1_6_1" => main_1_6_1(),
-        #[cfg(feature = "chapter-1")]
-        "1_6_2" => main_1_6_2(),
-        #[cfg(feature = "chapter-1")]
-        "1_6_3" => main_1_6_3(),
-        #[cfg(feature = "chapter-1")]
-        "1_7_1" => main_1_7_1(),
-        #[cfg(feature = "chapter-1")]
-        "1_7_2" => main_1_7_2(),
-        #[cfg(feature = "chapter-1")]
-        "1_7_3" => main_1_7_3(),
-        #[cfg(feature = "chapter-1")]
-        "1_7_4" => main_1_7_4(),
+fn exit_with_usage() -> ! {
+    println!("{}", USAGE);
+    process::exit(1);
+}
 
-        #[cfg(feature = "chapter-2")]
-        "2_1" => main_2_1(),
-        #[cfg(feature = "chapter-2")]
-        "2_2_1" => main_2_2_1(),
-        #[cfg(feature = "chapter-2")]
-        "2_2_2" => main_2_2_2(),
-        #[cfg(feature = "chapter-2")]
-        "2_3_1" => main_2_3_1(),
-        #[cfg(feature = "chapter-2")]
-        "2_4_1" => main_2_4_1(),
-        #[cfg(feature = "chapter-2")]
-        "2_4_2" => main_2_4_2(),
-        #[cfg(feature = "chapter-2")]
-        "2_5_1" => main_2_5_1(),
-        #[cfg(feature = "chapter-2")]
-        "2_5_2" => main_2_5_2(),
-        #[cfg(feature = "chapter-2")]
-        "2_5_3" => main_2_5_3(),
-        #[cfg(feature = "chapter-2")]
-        "2_5_4" => main_2_5_4(),
-        #[cfg(feature = "chapter-2")]
-        "2_6" => main_2_6(),
+fn list_tutorials() {
+    let mut chapter = 0;
+    for tutorial in tutorials() {
+        if tutorial.chapter != chapter {
+            chapter = tutorial.chapter;
+            println!("chapter {}", chapter);
+        }
+        println!("    {:<8}{}", tutorial.id, tutorial.title);
+    }
+}
 
-        #[cfg(feature = "chapter-3")]
-        "3_1" => main_3_1(),
+fn run_tutorial(query: &str) {
+    let found = find_tutorials(query);
+    match found.as_slice() {
+        [tutorial] => (tutorial.main)(),
+        [] => {
+            println!("unknown tutorial `{}`, see `list` for all tutorials", query);
+            process::exit(1);
+        }
+        candidates => {
+            println!("`{}` matches several tutorials:", query);
+            for tutorial in candidates {
+                println!("    {:<8}{}", tutorial.id, tutorial.title);
+            }
+            process::exit(1);
+        }
+    }
+}
+
+/// Runs the tutorials of a chapter one after the other. Each one gets its own
+/// process, as glfw can only be initialized once per process.
+fn run_chapter(chapter: &str, headless_args: &[String]) {
+    let tutorials = match chapter.parse() {
+        Ok(chapter) => chapter_tutorials(chapter),
+        Err(_) => Vec::new(),
+    };
+    if tutorials.is_empty() {
+        println!("no tutorials in chapter `{}`, see `list` for all tutorials", chapter);
+        process::exit(1);
+    }
 
-        #[cfg(feature = "chapter-4")]
-        "4_1_1" => main_4_1_1(),
-        "4_1_2" => main_4_1_2(),
-        "4_2" => main_4_2(),
-        "4_3_1" => main_4_3_1(),
+    let exe = env::current_exe().expect("Failed to locate the current executable");
+    let mut failed = Vec::new();
+    for tutorial in tutorials {
+        println!("{} {}", tutorial.id, tutorial.title);
+        let status = Command::new(&exe)
+            .args(headless_args)
+            .args(&["run", tutorial.id])
+            .status()
+            .expect("Failed to run tutorial");
+        if !status.success() {
+            println!("{} FAILED ({})", tutorial.id, status);
+            failed.push(tutorial.id);
+        }
+    }
 
-        _ => println!("unknown tutorial id"),
+    if !failed.is_empty() {
+        println!("failed: {}", failed.join(", "));
+        process::exit(1);
     }
 }
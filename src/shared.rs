@@ -1,21 +1,78 @@
+pub use buffer::*;
 pub use camera::*;
 pub use common::*;
+pub use framebuffer::*;
+pub use golden::*;
+pub use hdr::*;
+pub use headless::*;
 pub use macros::*;
 pub use mesh::*;
 pub use model::*;
+pub use point_shadow::*;
+pub use post_process::*;
+pub use preprocessor::*;
+pub use reflection::*;
+pub use reloadable_shader::*;
 pub use shader::*;
+pub use shader_error::*;
+pub use shadow_map::*;
+pub use skybox::*;
+pub use std140::*;
+pub use tangents::*;
+pub use transparency::*;
+pub use uniform::*;
+pub use uniform_buffer::*;
 pub use utils::*;
+pub use winding::*;
+
+pub mod buffer;
 
 pub mod camera;
 
 pub mod common;
 
+pub mod framebuffer;
+
+pub mod golden;
+
+pub mod hdr;
+
+pub mod headless;
+
 pub mod macros;
 
 pub mod mesh;
 
 pub mod model;
 
+pub mod point_shadow;
+
+pub mod post_process;
+
+pub mod preprocessor;
+
+pub mod reflection;
+
+pub mod reloadable_shader;
+
 pub mod shader;
 
+pub mod shader_error;
+
+pub mod shadow_map;
+
+pub mod skybox;
+
+pub mod std140;
+
+pub mod tangents;
+
+pub mod transparency;
+
+pub mod uniform;
+
+pub mod uniform_buffer;
+
 pub mod utils;
+
+pub mod winding;
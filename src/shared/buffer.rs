@@ -0,0 +1,235 @@
+#![allow(dead_code)]
+
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Deref, DerefMut, Range};
+use std::os::raw::c_void;
+use std::ptr;
+use std::slice;
+
+use gl;
+use gl::types::*;
+
+/// A buffer object holding `len` elements of `T`, e.g. `Buffer<f32>` for
+/// vertex data or `Buffer<u32>` for indices. Offsets and lengths are in
+/// elements, not bytes.
+///
+/// Unlike `gl::BufferData` on a packed array, which is all `TutorialGeometry`
+/// and `Mesh` do, the contents can be updated in place with `set_sub_data`,
+/// mapped with `map_range` or copied between buffers with `copy_to`.
+pub struct Buffer<T: Copy> {
+    pub id: u32,
+    target: GLenum,
+    usage: GLenum,
+    len: usize,
+    element: PhantomData<T>,
+}
+
+impl<T: Copy> Buffer<T> {
+    /// Creates a buffer filled with `data`; `usage` is a hint like `gl::STATIC_DRAW`
+    /// or `gl::DYNAMIC_DRAW` for data that is updated every frame
+    pub unsafe fn new(target: GLenum, data: &[T], usage: GLenum) -> Buffer<T> {
+        let mut buffer = Buffer::empty(target, usage);
+        buffer.set_data(data);
+        buffer
+    }
+
+    /// Creates a buffer of `len` elements without initializing them
+    pub unsafe fn with_len(target: GLenum, len: usize, usage: GLenum) -> Buffer<T> {
+        let mut buffer = Buffer::empty(target, usage);
+        buffer.allocate(len, ptr::null());
+        buffer
+    }
+
+    unsafe fn empty(target: GLenum, usage: GLenum) -> Buffer<T> {
+        let mut id = 0;
+        gl::GenBuffers(1, &mut id);
+        Buffer { id, target, usage, len: 0, element: PhantomData }
+    }
+
+    unsafe fn allocate(&mut self, len: usize, data: *const c_void) {
+        self.bind();
+        gl::BufferData(self.target, (len * mem::size_of::<T>()) as GLsizeiptr, data, self.usage);
+        self.len = len;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn target(&self) -> GLenum {
+        self.target
+    }
+
+    pub unsafe fn bind(&self) {
+        gl::BindBuffer(self.target, self.id);
+    }
+
+    /// Replaces the whole contents, reallocating the storage
+    pub unsafe fn set_data(&mut self, data: &[T]) {
+        self.allocate(data.len(), data.as_ptr() as *const c_void);
+    }
+
+    /// Overwrites the elements starting at `offset` with `data` using `gl::BufferSubData`
+    pub unsafe fn set_sub_data(&self, offset: usize, data: &[T]) {
+        self.check_range(offset..offset + data.len());
+        self.bind();
+        gl::BufferSubData(
+            self.target,
+            (offset * mem::size_of::<T>()) as GLintptr,
+            mem::size_of_val(data) as GLsizeiptr,
+            data.as_ptr() as *const c_void,
+        );
+    }
+
+    /// Maps the elements in `range` into client memory, `access` being the
+    /// `gl::MapBufferRange` flags, e.g. `gl::MAP_WRITE_BIT | gl::MAP_INVALIDATE_RANGE_BIT`
+    /// to overwrite them. The mapping derefs to a slice and is unmapped when
+    /// dropped; reading needs `gl::MAP_READ_BIT`, writing `gl::MAP_WRITE_BIT`.
+    /// Reading through a write-only mapping panics, but its `len` can be
+    /// queried either way.
+    pub unsafe fn map_range(&mut self, range: Range<usize>, access: GLbitfield) -> BufferMapping<'_, T> {
+        self.check_range(range.clone());
+        self.bind();
+        let data = gl::MapBufferRange(
+            self.target,
+            (range.start * mem::size_of::<T>()) as GLintptr,
+            ((range.end - range.start) * mem::size_of::<T>()) as GLsizeiptr,
+            access,
+        );
+        if data.is_null() {
+            panic!("ERROR::BUFFER: failed to map elements {:?} of buffer {}", range, self.id);
+        }
+        BufferMapping { buffer: self, data: data as *mut T, len: range.end - range.start, access }
+    }
+
+    /// Copies `len` elements starting at `offset` to `dst_offset` in `dst`
+    /// with `gl::CopyBufferSubData`, without a round trip through client memory
+    pub unsafe fn copy_to(&self, offset: usize, dst: &Buffer<T>, dst_offset: usize, len: usize) {
+        self.check_range(offset..offset + len);
+        dst.check_range(dst_offset..dst_offset + len);
+        gl::BindBuffer(gl::COPY_READ_BUFFER, self.id);
+        gl::BindBuffer(gl::COPY_WRITE_BUFFER, dst.id);
+        gl::CopyBufferSubData(
+            gl::COPY_READ_BUFFER,
+            gl::COPY_WRITE_BUFFER,
+            (offset * mem::size_of::<T>()) as GLintptr,
+            (dst_offset * mem::size_of::<T>()) as GLintptr,
+            (len * mem::size_of::<T>()) as GLsizeiptr,
+        );
+    }
+
+    fn check_range(&self, range: Range<usize>) {
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "ERROR::BUFFER: elements {:?} out of range for buffer {} of length {}",
+            range,
+            self.id,
+            self.len
+        );
+    }
+}
+
+impl Buffer<f32> {
+    /// Creates a vertex buffer with non-interleaved (batched) attributes: all
+    /// values of the first attribute, then all of the second one and so on,
+    /// e.g. `[(0, 3, &positions), (1, 2, &tex_coords)]` for `(location,
+    /// components, data)`. Points the attributes of the currently bound VAO at
+    /// their blocks.
+    ///
+    /// Use `attribute_offset` to update a single attribute with `set_sub_data`
+    /// or `map_range` later on.
+    pub unsafe fn batched(attributes: &[(u32, i32, &[f32])], usage: GLenum) -> Buffer<f32> {
+        let vertices = attributes.first().map_or(0, |(_, components, data)| data.len() / *components as usize);
+        let len = attributes.iter().map(|(_, _, data)| data.len()).sum();
+        let buffer = Buffer::with_len(gl::ARRAY_BUFFER, len, usage);
+
+        let mut offset = 0;
+        for (location, components, data) in attributes {
+            assert_eq!(
+                data.len(),
+                vertices * *components as usize,
+                "ERROR::BUFFER: attribute {} has a different number of vertices than the first one",
+                location
+            );
+            buffer.set_sub_data(offset, data);
+            gl::EnableVertexAttribArray(*location);
+            gl::VertexAttribPointer(
+                *location,
+                *components,
+                gl::FLOAT,
+                gl::FALSE,
+                *components * mem::size_of::<GLfloat>() as GLsizei,
+                (offset * mem::size_of::<GLfloat>()) as *const c_void,
+            );
+            offset += data.len();
+        }
+        buffer
+    }
+}
+
+/// Offset of attribute `index` in a `Buffer::batched` buffer holding `vertices`
+/// vertices, given the number of components of all attributes
+pub fn attribute_offset(components: &[i32], index: usize, vertices: usize) -> usize {
+    components[..index].iter().map(|components| *components as usize * vertices).sum()
+}
+
+impl<T: Copy> Drop for Buffer<T> {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteBuffers(1, &self.id);
+        }
+    }
+}
+
+/// A mapped range of a `Buffer`, see `Buffer::map_range`. Borrows the buffer
+/// mutably, so it can't be drawn from or mapped again while this is alive.
+pub struct BufferMapping<'a, T: Copy> {
+    buffer: &'a mut Buffer<T>,
+    data: *mut T,
+    len: usize,
+    access: GLbitfield,
+}
+
+impl<'a, T: Copy> BufferMapping<'a, T> {
+    /// number of mapped elements, available whatever the access flags
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<'a, T: Copy> Deref for BufferMapping<'a, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        assert!(self.access & gl::MAP_READ_BIT != 0, "ERROR::BUFFER: mapping is not readable");
+        unsafe { slice::from_raw_parts(self.data, self.len) }
+    }
+}
+
+impl<'a, T: Copy> DerefMut for BufferMapping<'a, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        assert!(self.access & gl::MAP_WRITE_BIT != 0, "ERROR::BUFFER: mapping is not writable");
+        unsafe { slice::from_raw_parts_mut(self.data, self.len) }
+    }
+}
+
+impl<'a, T: Copy> Drop for BufferMapping<'a, T> {
+    fn drop(&mut self) {
+        unsafe {
+            self.buffer.bind();
+            if gl::UnmapBuffer(self.buffer.target) == gl::FALSE {
+                // the contents got corrupted while mapped, e.g. by a display mode change
+                println!("WARNING::BUFFER: contents of buffer {} were lost while mapped", self.buffer.id);
+            }
+        }
+    }
+}
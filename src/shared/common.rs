@@ -1,5 +1,7 @@
 #![allow(dead_code)]
 
+use std::error::Error;
+use std::fmt;
 use std::os::raw::c_void;
 use std::path::Path;
 use std::sync::mpsc::Receiver;
@@ -10,6 +12,7 @@ use image::{DynamicImage, GenericImageView};
 
 use crate::camera::Camera;
 use crate::camera::CameraMovement::*;
+use crate::shared::handle_reload_key;
 
 /// Event processing function as introduced in 1.7.4 (Camera Class) and used in
 /// most later tutorials
@@ -20,7 +23,23 @@ pub fn process_events(
     last_y: &mut f32,
     camera: &mut Camera,
 ) {
+    process_events_with(events, first_mouse, last_x, last_y, camera, |_| {});
+}
+
+/// Same as `process_events`, but also passes every event to `on_event`, e.g.
+/// to resize framebuffers along with the window
+pub fn process_events_with<F>(
+    events: &Receiver<(f64, glfw::WindowEvent)>,
+    first_mouse: &mut bool,
+    last_x: &mut f32,
+    last_y: &mut f32,
+    camera: &mut Camera,
+    mut on_event: F,
+) where
+    F: FnMut(&glfw::WindowEvent),
+{
     for (_, event) in glfw::flush_messages(events) {
+        on_event(&event);
         match event {
             glfw::WindowEvent::FramebufferSize(width, height) => {
                 // make sure the viewport matches the new window dimensions; note that width and
@@ -70,17 +89,33 @@ pub fn process_input(window: &mut glfw::Window, delta_time: f32, camera: &mut Ca
     if window.get_key(Key::D) == Action::Press {
         camera.process_keyboard(RIGHT, delta_time);
     }
+
+    // F5 forces every `ReloadableShader` to recompile
+    handle_reload_key(window.get_key(Key::F5) == Action::Press);
 }
 
 /// utility function for loading a 2D texture from file
 /// ---------------------------------------------------
 #[allow(dead_code)]
 pub unsafe fn load_texture(path: &str) -> u32 {
+    load_texture_with_gamma(path, false)
+}
+
+/// Loads a texture whose colors are stored in sRGB space, as most color
+/// (diffuse) textures are: OpenGL converts them to linear space when they're
+/// sampled, so lighting can be computed on linear values. Don't use it for
+/// data like normal or specular maps, those are linear already.
+pub unsafe fn load_texture_srgb(path: &str) -> u32 {
+    load_texture_with_gamma(path, true)
+}
+
+unsafe fn load_texture_with_gamma(path: &str, gamma_correction: bool) -> u32 {
     let mut texture_id = 0;
 
     gl::GenTextures(1, &mut texture_id);
     let img = image::open(&Path::new(path)).expect("Texture failed to load");
     let format = image_format(&img);
+    let internal_format = if gamma_correction { srgb_internal_format(format) } else { format };
 
     // let data = img.raw_pixels();
     let data = img.to_bytes();
@@ -89,7 +124,7 @@ pub unsafe fn load_texture(path: &str) -> u32 {
     gl::TexImage2D(
         gl::TEXTURE_2D,
         0,
-        format as i32,
+        internal_format as i32,
         img.width() as i32,
         img.height() as i32,
         0,
@@ -107,6 +142,116 @@ pub unsafe fn load_texture(path: &str) -> u32 {
     texture_id
 }
 
+/// Names of the cubemap faces in the order `load_cubemap` expects them, i.e.
+/// the order of the `TEXTURE_CUBE_MAP_POSITIVE_X` .. `NEGATIVE_Z` targets:
+/// +X (right), -X (left), +Y (top), -Y (bottom), +Z (front), -Z (back)
+pub const CUBEMAP_FACES: [&str; 6] = ["right", "left", "top", "bottom", "front", "back"];
+
+/// same order, as some skyboxes name their faces
+const CUBEMAP_FACES_AXES: [&str; 6] = ["posx", "negx", "posy", "negy", "posz", "negz"];
+
+#[derive(Debug)]
+pub enum CubemapError {
+    /// a face image couldn't be opened or decoded
+    Image { path: String, error: image::ImageError },
+    /// a face is named after a different face than the one it's loaded as, e.g.
+    /// "top.jpg" given as the first (right) face
+    FaceOrder { path: String, index: usize },
+    /// cubemap faces have to be square
+    NotSquare { path: String, width: u32, height: u32 },
+    /// all faces have to be the same size as the first one
+    SizeMismatch { path: String, size: u32, expected: u32 },
+}
+
+impl fmt::Display for CubemapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CubemapError::Image { path, error } => write!(f, "ERROR::CUBEMAP::IMAGE: {}: {}", path, error),
+            CubemapError::FaceOrder { path, index } => write!(
+                f,
+                "ERROR::CUBEMAP::FACE_ORDER: {} given as face {} ({}), expected order is {}",
+                path,
+                index,
+                CUBEMAP_FACES[*index],
+                CUBEMAP_FACES.join(", ")
+            ),
+            CubemapError::NotSquare { path, width, height } => {
+                write!(f, "ERROR::CUBEMAP::NOT_SQUARE: {} is {}x{}", path, width, height)
+            }
+            CubemapError::SizeMismatch { path, size, expected } => write!(
+                f,
+                "ERROR::CUBEMAP::SIZE_MISMATCH: {} is {}x{}, the first face is {}x{}",
+                path, size, size, expected, expected
+            ),
+        }
+    }
+}
+
+impl Error for CubemapError {}
+
+/// utility function for loading a cubemap texture from 6 individual texture faces,
+/// in the order of `CUBEMAP_FACES`
+/// --------------------------------------------------------------------------------
+pub unsafe fn load_cubemap(faces: &[&str; 6]) -> u32 {
+    try_load_cubemap(faces).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Same as `load_cubemap`, but returns an error instead of panicking. All
+/// faces are checked before anything is uploaded.
+pub unsafe fn try_load_cubemap(faces: &[&str; 6]) -> Result<u32, CubemapError> {
+    let mut images = Vec::with_capacity(6);
+    for (index, path) in faces.iter().enumerate() {
+        // a face named like another face is almost certainly in the wrong place
+        let stem = Path::new(path).file_stem().and_then(|stem| stem.to_str()).unwrap_or("").to_lowercase();
+        let named = CUBEMAP_FACES.iter().position(|face| *face == stem);
+        let named = named.or_else(|| CUBEMAP_FACES_AXES.iter().position(|face| *face == stem));
+        if matches!(named, Some(named) if named != index) {
+            return Err(CubemapError::FaceOrder { path: path.to_string(), index });
+        }
+
+        let img = image::open(path).map_err(|error| CubemapError::Image { path: path.to_string(), error })?;
+        let (width, height) = img.dimensions();
+        if width != height {
+            return Err(CubemapError::NotSquare { path: path.to_string(), width, height });
+        }
+        if let Some(first) = images.first() {
+            let expected = GenericImageView::width(first);
+            if width != expected {
+                return Err(CubemapError::SizeMismatch { path: path.to_string(), size: width, expected });
+            }
+        }
+        images.push(img);
+    }
+
+    let mut texture_id = 0;
+    gl::GenTextures(1, &mut texture_id);
+    gl::BindTexture(gl::TEXTURE_CUBE_MAP, texture_id);
+
+    for (i, img) in images.iter().enumerate() {
+        let format = image_format(img);
+        let data = img.to_bytes();
+        gl::TexImage2D(
+            gl::TEXTURE_CUBE_MAP_POSITIVE_X + i as u32,
+            0,
+            format as i32,
+            img.width() as i32,
+            img.height() as i32,
+            0,
+            format,
+            gl::UNSIGNED_BYTE,
+            &data[0] as *const u8 as *const c_void,
+        );
+    }
+
+    gl::TexParameteri(gl::TEXTURE_CUBE_MAP, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
+    gl::TexParameteri(gl::TEXTURE_CUBE_MAP, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+    gl::TexParameteri(gl::TEXTURE_CUBE_MAP, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as i32);
+    gl::TexParameteri(gl::TEXTURE_CUBE_MAP, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as i32);
+    gl::TexParameteri(gl::TEXTURE_CUBE_MAP, gl::TEXTURE_WRAP_R, gl::CLAMP_TO_EDGE as i32);
+
+    Ok(texture_id)
+}
+
 pub fn image_format(img: &DynamicImage) -> u32 {
     match img {
         ImageLuma8(_) => gl::RED,
@@ -116,3 +261,13 @@ pub fn image_format(img: &DynamicImage) -> u32 {
         _ => gl::RGBA,
     }
 }
+
+/// The sRGB internal format for a pixel format returned by `image_format`.
+/// There are only sRGB formats for RGB and RGBA, the others stay linear.
+pub fn srgb_internal_format(format: u32) -> u32 {
+    match format {
+        gl::RGB => gl::SRGB8,
+        gl::RGBA => gl::SRGB8_ALPHA8,
+        _ => format,
+    }
+}
@@ -0,0 +1,345 @@
+#![allow(dead_code)]
+
+use std::error::Error;
+use std::fmt;
+use std::ptr;
+
+use gl;
+use gl::types::*;
+
+/// What to use for the depth (and stencil) buffer of a `Framebuffer`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DepthAttachment {
+    None,
+    /// a 24 bit depth + 8 bit stencil renderbuffer; can't be sampled, but
+    /// that's rarely needed
+    DepthStencilRenderbuffer,
+    DepthRenderbuffer,
+    /// a depth texture, e.g. for shadow maps
+    DepthTexture,
+}
+
+#[derive(Debug)]
+pub enum FramebufferError {
+    /// `glCheckFramebufferStatus` didn't return `GL_FRAMEBUFFER_COMPLETE`
+    Incomplete { status: GLenum },
+    /// zero width or height
+    EmptySize { width: i32, height: i32 },
+}
+
+impl fmt::Display for FramebufferError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FramebufferError::Incomplete { status } => {
+                let reason = match *status {
+                    gl::FRAMEBUFFER_UNDEFINED => "GL_FRAMEBUFFER_UNDEFINED",
+                    gl::FRAMEBUFFER_INCOMPLETE_ATTACHMENT => "GL_FRAMEBUFFER_INCOMPLETE_ATTACHMENT",
+                    gl::FRAMEBUFFER_INCOMPLETE_MISSING_ATTACHMENT => "GL_FRAMEBUFFER_INCOMPLETE_MISSING_ATTACHMENT",
+                    gl::FRAMEBUFFER_INCOMPLETE_DRAW_BUFFER => "GL_FRAMEBUFFER_INCOMPLETE_DRAW_BUFFER",
+                    gl::FRAMEBUFFER_INCOMPLETE_READ_BUFFER => "GL_FRAMEBUFFER_INCOMPLETE_READ_BUFFER",
+                    gl::FRAMEBUFFER_UNSUPPORTED => "GL_FRAMEBUFFER_UNSUPPORTED",
+                    gl::FRAMEBUFFER_INCOMPLETE_MULTISAMPLE => "GL_FRAMEBUFFER_INCOMPLETE_MULTISAMPLE",
+                    gl::FRAMEBUFFER_INCOMPLETE_LAYER_TARGETS => "GL_FRAMEBUFFER_INCOMPLETE_LAYER_TARGETS",
+                    _ => "unknown status",
+                };
+                write!(f, "ERROR::FRAMEBUFFER:: Framebuffer is not complete: {} (0x{:x})", reason, status)
+            }
+            FramebufferError::EmptySize { width, height } => {
+                write!(f, "ERROR::FRAMEBUFFER:: Framebuffer size {}x{} is empty", width, height)
+            }
+        }
+    }
+}
+
+impl Error for FramebufferError {}
+
+/// Describes the attachments of a `Framebuffer`, see `Framebuffer::builder`
+#[derive(Debug, Clone)]
+pub struct FramebufferBuilder {
+    width: i32,
+    height: i32,
+    /// internal formats of the color textures, in attachment order
+    color_formats: Vec<GLenum>,
+    depth: DepthAttachment,
+    /// 0 for regular textures, otherwise the number of samples per pixel
+    samples: i32,
+}
+
+impl FramebufferBuilder {
+    /// Adds a color texture with the given internal format (e.g. `gl::RGB`,
+    /// or `gl::RGBA16F` for HDR) as the next color attachment
+    pub fn color_texture(mut self, internal_format: GLenum) -> Self {
+        self.color_formats.push(internal_format);
+        self
+    }
+
+    pub fn depth(mut self, depth: DepthAttachment) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    pub fn depth_stencil_renderbuffer(self) -> Self {
+        self.depth(DepthAttachment::DepthStencilRenderbuffer)
+    }
+
+    /// Multisampled attachments with `samples` samples per pixel, for MSAA.
+    /// Color textures become `TEXTURE_2D_MULTISAMPLE`, which can't be sampled
+    /// like regular textures: either resolve them with `Framebuffer::blit_to`
+    /// or read individual samples with a `sampler2DMS` and `texelFetch`.
+    pub fn samples(mut self, samples: i32) -> Self {
+        self.samples = samples;
+        self
+    }
+
+    /// Creates the framebuffer. Leaves the default framebuffer bound.
+    pub unsafe fn build(self) -> Result<Framebuffer, FramebufferError> {
+        let mut framebuffer =
+            Framebuffer { id: 0, color_textures: Vec::new(), depth_texture: None, renderbuffer: None, spec: self };
+        framebuffer.create()?;
+        Ok(framebuffer)
+    }
+}
+
+/// An offscreen render target with texture color attachments and an optional
+/// depth/stencil buffer. Deletes its GL objects when dropped.
+///
+/// ```ignore
+/// let framebuffer = Framebuffer::builder(width, height)
+///     .color_texture(gl::RGB)
+///     .depth_stencil_renderbuffer()
+///     .build()?;
+/// ```
+pub struct Framebuffer {
+    pub id: u32,
+    color_textures: Vec<u32>,
+    depth_texture: Option<u32>,
+    renderbuffer: Option<u32>,
+    spec: FramebufferBuilder,
+}
+
+impl Framebuffer {
+    pub fn builder(width: i32, height: i32) -> FramebufferBuilder {
+        FramebufferBuilder { width, height, color_formats: Vec::new(), depth: DepthAttachment::None, samples: 0 }
+    }
+
+    /// Renders into this framebuffer from now on, with the viewport set to its size
+    pub unsafe fn bind(&self) {
+        gl::BindFramebuffer(gl::FRAMEBUFFER, self.id);
+        gl::Viewport(0, 0, self.spec.width, self.spec.height);
+    }
+
+    /// Goes back to rendering to the window. `width`/`height` are the window's
+    /// framebuffer size, for the viewport.
+    pub unsafe fn bind_default(width: i32, height: i32) {
+        gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+        gl::Viewport(0, 0, width, height);
+    }
+
+    pub fn width(&self) -> i32 {
+        self.spec.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.spec.height
+    }
+
+    /// Texture of color attachment `index`
+    pub fn color_texture(&self, index: usize) -> u32 {
+        self.color_textures[index]
+    }
+
+    /// 0 if the framebuffer isn't multisampled
+    pub fn samples(&self) -> i32 {
+        self.spec.samples
+    }
+
+    /// What to bind the attachment textures to, `TEXTURE_2D` or `TEXTURE_2D_MULTISAMPLE`
+    pub fn texture_target(&self) -> GLenum {
+        texture_target(self.spec.samples)
+    }
+
+    /// Copies the color (and with `mask` also depth/stencil) contents into
+    /// `target`, resolving multisampled attachments to one sample per pixel.
+    /// Stretches to the target's size if it differs.
+    pub unsafe fn blit_to(&self, target: &Framebuffer, mask: GLbitfield) {
+        self.blit(target.id, target.width(), target.height(), mask);
+    }
+
+    /// Like `blit_to`, but into the window's framebuffer of the given size,
+    /// which is bound afterwards
+    pub unsafe fn blit_to_default(&self, width: i32, height: i32, mask: GLbitfield) {
+        self.blit(0, width, height, mask);
+        Framebuffer::bind_default(width, height);
+    }
+
+    unsafe fn blit(&self, target: u32, width: i32, height: i32, mask: GLbitfield) {
+        gl::BindFramebuffer(gl::READ_FRAMEBUFFER, self.id);
+        gl::BindFramebuffer(gl::DRAW_FRAMEBUFFER, target);
+        // depth and stencil can only be copied 1:1
+        let filter = if mask == gl::COLOR_BUFFER_BIT { gl::LINEAR } else { gl::NEAREST };
+        gl::BlitFramebuffer(0, 0, self.width(), self.height(), 0, 0, width, height, mask, filter);
+    }
+
+    /// Only set for `DepthAttachment::DepthTexture`
+    pub fn depth_texture(&self) -> Option<u32> {
+        self.depth_texture
+    }
+
+    /// Recreates all attachments with the new size. The contents are lost.
+    pub unsafe fn resize(&mut self, width: i32, height: i32) -> Result<(), FramebufferError> {
+        if (width, height) == (self.spec.width, self.spec.height) {
+            return Ok(());
+        }
+        self.delete();
+        self.spec.width = width;
+        self.spec.height = height;
+        self.create()
+    }
+
+    /// Resizes on `FramebufferSize` events, so the framebuffer keeps matching
+    /// the window. Minimized windows report a size of 0, which is ignored.
+    pub unsafe fn handle_event(&mut self, event: &glfw::WindowEvent) -> Result<(), FramebufferError> {
+        match *event {
+            glfw::WindowEvent::FramebufferSize(width, height) if width > 0 && height > 0 => self.resize(width, height),
+            _ => Ok(()),
+        }
+    }
+
+    unsafe fn create(&mut self) -> Result<(), FramebufferError> {
+        let (width, height) = (self.spec.width, self.spec.height);
+        if width <= 0 || height <= 0 {
+            return Err(FramebufferError::EmptySize { width, height });
+        }
+
+        gl::GenFramebuffers(1, &mut self.id);
+        gl::BindFramebuffer(gl::FRAMEBUFFER, self.id);
+
+        // create the color attachment textures
+        let (samples, target) = (self.spec.samples, self.texture_target());
+        for (i, internal_format) in self.spec.color_formats.iter().enumerate() {
+            let (format, type_) = pixel_format(*internal_format);
+            let texture = create_texture(width, height, samples, *internal_format, format, type_);
+            gl::FramebufferTexture2D(gl::FRAMEBUFFER, gl::COLOR_ATTACHMENT0 + i as u32, target, texture, 0);
+            self.color_textures.push(texture);
+        }
+        // without color attachments (e.g. depth only for shadow maps) there's nothing to draw to or read from
+        if self.spec.color_formats.is_empty() {
+            gl::DrawBuffer(gl::NONE);
+            gl::ReadBuffer(gl::NONE);
+        } else if self.spec.color_formats.len() > 1 {
+            let attachments: Vec<_> =
+                (0..self.spec.color_formats.len() as u32).map(|i| gl::COLOR_ATTACHMENT0 + i).collect();
+            gl::DrawBuffers(attachments.len() as i32, attachments.as_ptr());
+        }
+
+        // create the depth (and stencil) attachment
+        match self.spec.depth {
+            DepthAttachment::None => {}
+            DepthAttachment::DepthStencilRenderbuffer => {
+                let rbo = create_renderbuffer(width, height, samples, gl::DEPTH24_STENCIL8);
+                gl::FramebufferRenderbuffer(gl::FRAMEBUFFER, gl::DEPTH_STENCIL_ATTACHMENT, gl::RENDERBUFFER, rbo);
+                self.renderbuffer = Some(rbo);
+            }
+            DepthAttachment::DepthRenderbuffer => {
+                let rbo = create_renderbuffer(width, height, samples, gl::DEPTH_COMPONENT24);
+                gl::FramebufferRenderbuffer(gl::FRAMEBUFFER, gl::DEPTH_ATTACHMENT, gl::RENDERBUFFER, rbo);
+                self.renderbuffer = Some(rbo);
+            }
+            DepthAttachment::DepthTexture => {
+                let texture =
+                    create_texture(width, height, samples, gl::DEPTH_COMPONENT, gl::DEPTH_COMPONENT, gl::FLOAT);
+                gl::FramebufferTexture2D(gl::FRAMEBUFFER, gl::DEPTH_ATTACHMENT, target, texture, 0);
+                self.depth_texture = Some(texture);
+            }
+        }
+
+        // now that we actually created the framebuffer and added all attachments we want to check if it is actually complete now
+        let status = gl::CheckFramebufferStatus(gl::FRAMEBUFFER);
+        gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+        if status != gl::FRAMEBUFFER_COMPLETE {
+            self.delete();
+            return Err(FramebufferError::Incomplete { status });
+        }
+        Ok(())
+    }
+
+    unsafe fn delete(&mut self) {
+        gl::DeleteFramebuffers(1, &self.id);
+        self.id = 0;
+        gl::DeleteTextures(self.color_textures.len() as i32, self.color_textures.as_ptr());
+        self.color_textures.clear();
+        if let Some(texture) = self.depth_texture.take() {
+            gl::DeleteTextures(1, &texture);
+        }
+        if let Some(rbo) = self.renderbuffer.take() {
+            gl::DeleteRenderbuffers(1, &rbo);
+        }
+    }
+}
+
+impl Drop for Framebuffer {
+    fn drop(&mut self) {
+        unsafe { self.delete() }
+    }
+}
+
+/// Format and type to go with an internal format when allocating a texture
+fn pixel_format(internal_format: GLenum) -> (GLenum, GLenum) {
+    match internal_format {
+        gl::RED | gl::R8 => (gl::RED, gl::UNSIGNED_BYTE),
+        gl::R16F | gl::R32F => (gl::RED, gl::FLOAT),
+        gl::RGB | gl::RGB8 | gl::SRGB8 => (gl::RGB, gl::UNSIGNED_BYTE),
+        gl::RGB16F | gl::RGB32F => (gl::RGB, gl::FLOAT),
+        gl::RGBA16F | gl::RGBA32F => (gl::RGBA, gl::FLOAT),
+        _ => (gl::RGBA, gl::UNSIGNED_BYTE),
+    }
+}
+
+fn texture_target(samples: i32) -> GLenum {
+    if samples > 0 {
+        gl::TEXTURE_2D_MULTISAMPLE
+    } else {
+        gl::TEXTURE_2D
+    }
+}
+
+unsafe fn create_texture(
+    width: i32,
+    height: i32,
+    samples: i32,
+    internal_format: GLenum,
+    format: GLenum,
+    type_: GLenum,
+) -> u32 {
+    let mut texture = 0;
+    gl::GenTextures(1, &mut texture);
+    if samples > 0 {
+        // multisample textures have neither filtering nor wrapping
+        gl::BindTexture(gl::TEXTURE_2D_MULTISAMPLE, texture);
+        gl::TexImage2DMultisample(gl::TEXTURE_2D_MULTISAMPLE, samples, internal_format, width, height, gl::TRUE);
+        gl::BindTexture(gl::TEXTURE_2D_MULTISAMPLE, 0);
+        return texture;
+    }
+    gl::BindTexture(gl::TEXTURE_2D, texture);
+    gl::TexImage2D(gl::TEXTURE_2D, 0, internal_format as i32, width, height, 0, format, type_, ptr::null());
+    gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
+    gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+    // post-processing kernels sample outside of the texture at the edges
+    gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as i32);
+    gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as i32);
+    gl::BindTexture(gl::TEXTURE_2D, 0);
+    texture
+}
+
+unsafe fn create_renderbuffer(width: i32, height: i32, samples: i32, internal_format: GLenum) -> u32 {
+    let mut rbo = 0;
+    gl::GenRenderbuffers(1, &mut rbo);
+    gl::BindRenderbuffer(gl::RENDERBUFFER, rbo);
+    if samples > 0 {
+        gl::RenderbufferStorageMultisample(gl::RENDERBUFFER, samples, internal_format, width, height);
+    } else {
+        gl::RenderbufferStorage(gl::RENDERBUFFER, internal_format, width, height);
+    }
+    gl::BindRenderbuffer(gl::RENDERBUFFER, 0);
+    rbo
+}
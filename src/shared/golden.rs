@@ -0,0 +1,201 @@
+#![allow(dead_code)]
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use image::{Rgb, RgbImage};
+
+/// Checked-in reference frames, one `<tutorial id>.png` per tutorial
+pub const GOLDEN_DIR: &str = "resources/golden";
+/// `gl-info` output of the renderer the reference frames were blessed on
+pub const GOLDEN_RENDERER_FILE: &str = "resources/golden/renderer.txt";
+/// Captured frames and diff images of the last run
+pub const GOLDEN_OUTPUT_DIR: &str = "target/golden";
+/// Frame of each tutorial that gets compared; the clock is pinned in headless
+/// mode, so this fixes `get_time` as well
+pub const GOLDEN_FRAME: u32 = 10;
+/// Largest difference per color channel that still counts as a match, to
+/// absorb rounding differences between GL drivers
+pub const GOLDEN_TOLERANCE: u8 = 3;
+
+/// Result of comparing a rendered frame against its reference image
+pub struct ImageComparison {
+    /// pixels with at least one channel differing by more than the tolerance
+    pub mismatched_pixels: usize,
+    /// largest channel difference found anywhere in the image
+    pub max_difference: u8,
+    /// the reference image dimmed to grayscale, with mismatched pixels in red
+    pub diff: RgbImage,
+}
+
+impl ImageComparison {
+    pub fn matches(&self) -> bool {
+        self.mismatched_pixels == 0
+    }
+}
+
+/// Compares two images pixel by pixel. Returns `None` if their sizes differ.
+pub fn compare_images(actual: &RgbImage, reference: &RgbImage, tolerance: u8) -> Option<ImageComparison> {
+    if actual.dimensions() != reference.dimensions() {
+        return None;
+    }
+
+    let mut mismatched_pixels = 0;
+    let mut max_difference = 0;
+    let mut diff = RgbImage::new(reference.width(), reference.height());
+    for ((a, r), d) in actual.pixels().zip(reference.pixels()).zip(diff.pixels_mut()) {
+        let difference = a.0.iter().zip(r.0.iter()).map(|(a, r)| a.max(r) - a.min(r)).max().unwrap();
+        max_difference = max_difference.max(difference);
+        *d = if difference > tolerance {
+            mismatched_pixels += 1;
+            Rgb([255, 0, 0])
+        } else {
+            let luma = ((r[0] as u32 * 299 + r[1] as u32 * 587 + r[2] as u32 * 114) / 1000 / 4) as u8;
+            Rgb([luma, luma, luma])
+        };
+    }
+
+    Some(ImageComparison { mismatched_pixels, max_difference, diff })
+}
+
+/// Renders the golden frame of every tutorial in `ids` headless and compares it
+/// against `GOLDEN_DIR/<id>.png`. Every tutorial runs in its own process, as
+/// glfw can only be initialized once per process. With `bless` the captured
+/// frames replace the references instead. Returns whether all tutorials passed.
+///
+/// Drivers round differently, so the comparison is skipped (and passes) when
+/// the current renderer isn't the one in `GOLDEN_RENDERER_FILE`.
+pub fn run_golden_tests(ids: &[String], bless: bool) -> bool {
+    let exe = env::current_exe().expect("Failed to locate the current executable");
+    fs::create_dir_all(GOLDEN_OUTPUT_DIR).expect("Failed to create golden output directory");
+
+    let output = Command::new(&exe).arg("gl-info").output().expect("Failed to run gl-info");
+    if !output.status.success() {
+        println!("FAILED to create a headless GL context, see README");
+        return false;
+    }
+    let renderer = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if bless {
+        fs::create_dir_all(GOLDEN_DIR).expect("Failed to create golden directory");
+        fs::write(GOLDEN_RENDERER_FILE, format!("{}\n", renderer)).expect("Failed to save the renderer");
+    } else if let Ok(blessed) = fs::read_to_string(GOLDEN_RENDERER_FILE) {
+        if blessed.trim() != renderer {
+            println!("skipped, the reference images were blessed on\n{}\nbut this is\n{}", blessed.trim(), renderer);
+            println!("run `golden --bless` to compare against references of this renderer");
+            return true;
+        }
+    }
+
+    let mut failures = 0;
+    for id in ids {
+        let actual_path = Path::new(GOLDEN_OUTPUT_DIR).join(format!("{}.png", id));
+        let reference_path = Path::new(GOLDEN_DIR).join(format!("{}.png", id));
+        let diff_path = Path::new(GOLDEN_OUTPUT_DIR).join(format!("{}.diff.png", id));
+        let _ = fs::remove_file(&actual_path);
+        let _ = fs::remove_file(&diff_path);
+
+        let status = Command::new(&exe)
+            .args(&["--headless", &GOLDEN_FRAME.to_string(), "--capture"])
+            .arg(&actual_path)
+            .arg(id)
+            .status()
+            .expect("Failed to run tutorial");
+        if !status.success() || !actual_path.exists() {
+            println!("{}: FAILED to render ({})", id, status);
+            failures += 1;
+            continue;
+        }
+
+        if bless {
+            fs::copy(&actual_path, &reference_path).expect("Failed to update reference image");
+            println!("{}: blessed {}", id, reference_path.display());
+            continue;
+        }
+
+        let reference = match image::open(&reference_path) {
+            Ok(reference) => reference.to_rgb8(),
+            Err(e) => {
+                println!("{}: FAILED, no reference image {} ({})", id, reference_path.display(), e);
+                failures += 1;
+                continue;
+            }
+        };
+        let actual = image::open(&actual_path).expect("Failed to load captured frame").to_rgb8();
+        match compare_images(&actual, &reference, GOLDEN_TOLERANCE) {
+            Some(comparison) if comparison.matches() => println!("{}: ok", id),
+            Some(comparison) => {
+                comparison.diff.save(&diff_path).expect("Failed to save diff image");
+                println!(
+                    "{}: FAILED, {} pixels differ by up to {}, see {}",
+                    id,
+                    comparison.mismatched_pixels,
+                    comparison.max_difference,
+                    diff_path.display()
+                );
+                failures += 1;
+            }
+            None => {
+                println!(
+                    "{}: FAILED, rendered {:?} but reference is {:?}",
+                    id,
+                    actual.dimensions(),
+                    reference.dimensions()
+                );
+                failures += 1;
+            }
+        }
+    }
+
+    println!("{} of {} tutorials passed", ids.len() - failures, ids.len());
+    failures == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(pixels: &[[u8; 3]]) -> RgbImage {
+        RgbImage::from_fn(pixels.len() as u32, 1, |x, _| Rgb(pixels[x as usize]))
+    }
+
+    #[test]
+    fn identical_images_match() {
+        let reference = image(&[[0, 0, 0], [10, 20, 30], [255, 255, 255]]);
+        let comparison = compare_images(&reference, &reference, 0).unwrap();
+        assert!(comparison.matches());
+        assert_eq!(comparison.max_difference, 0);
+    }
+
+    #[test]
+    fn differences_within_the_tolerance_match() {
+        let reference = image(&[[0, 0, 0], [10, 20, 30]]);
+        let actual = image(&[[3, 0, 0], [10, 17, 33]]);
+        let comparison = compare_images(&actual, &reference, 3).unwrap();
+        assert!(comparison.matches());
+        assert_eq!(comparison.max_difference, 3);
+        // the diff shows the reference dimmed to gray
+        assert_eq!(comparison.diff.get_pixel(1, 0), &Rgb([4, 4, 4]));
+    }
+
+    #[test]
+    fn differences_over_the_tolerance_are_marked_red() {
+        let reference = image(&[[0, 0, 0], [10, 20, 30], [255, 255, 255]]);
+        let actual = image(&[[4, 0, 0], [10, 20, 30], [255, 0, 255]]);
+        let comparison = compare_images(&actual, &reference, 3).unwrap();
+        assert!(!comparison.matches());
+        assert_eq!(comparison.mismatched_pixels, 2);
+        assert_eq!(comparison.max_difference, 255);
+        assert_eq!(comparison.diff.get_pixel(0, 0), &Rgb([255, 0, 0]));
+        assert_ne!(comparison.diff.get_pixel(1, 0), &Rgb([255, 0, 0]));
+        assert_eq!(comparison.diff.get_pixel(2, 0), &Rgb([255, 0, 0]));
+    }
+
+    #[test]
+    fn images_of_different_sizes_dont_compare() {
+        let reference = image(&[[0, 0, 0], [0, 0, 0]]);
+        assert!(compare_images(&image(&[[0, 0, 0]]), &reference, 255).is_none());
+        assert!(compare_images(&RgbImage::new(2, 2), &reference, 255).is_none());
+    }
+}
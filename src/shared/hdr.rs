@@ -0,0 +1,214 @@
+#![allow(dead_code)]
+
+use std::os::raw::c_void;
+
+use gl;
+use glfw::{Action, Key};
+
+use crate::shared::{create_quad, Framebuffer, FramebufferError, Shader};
+
+/// Size of the (square) texture the scene's luminance is averaged in
+const LUMINANCE_SIZE: i32 = 256;
+/// The 1x1 mipmap level of that texture
+const LUMINANCE_AVERAGE_LEVEL: i32 = 8;
+
+/// Range auto exposure stays in, so a black screen doesn't get amplified without bounds
+const MIN_EXPOSURE: f32 = 0.01;
+const MAX_EXPOSURE: f32 = 100.0;
+
+/// How `HdrTarget` maps HDR colors into the [0, 1] range of the window, see
+/// src/shared/shaders/tonemap.fsh
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToneMapping {
+    /// just clamps, like rendering into the window directly
+    None,
+    /// `color / (color + 1)`
+    Reinhard,
+    /// `1 - exp(-color)`
+    Exposure,
+    /// a fit of the filmic curve of the Academy Color Encoding System
+    Aces,
+}
+
+impl ToneMapping {
+    pub const ALL: [ToneMapping; 4] =
+        [ToneMapping::None, ToneMapping::Reinhard, ToneMapping::Exposure, ToneMapping::Aces];
+
+    /// the `#define` selecting this operator in src/shared/shaders/tonemap.fsh
+    pub fn define(self) -> (&'static str, &'static str) {
+        let value = match self {
+            ToneMapping::None => "0",
+            ToneMapping::Reinhard => "1",
+            ToneMapping::Exposure => "2",
+            ToneMapping::Aces => "3",
+        };
+        ("TONE_MAPPING", value)
+    }
+}
+
+/// A floating point (RGBA16F) render target, so colors brighter than 1.0
+/// survive until they are tone mapped into the window. Renders with
+/// `exposure`, which Q and E adjust in `process_input`; with `auto_exposure`
+/// it adapts to the average luminance of the scene over time instead.
+///
+/// ```ignore
+/// hdr.begin();
+/// // draw the scene as usual, with lights as bright as they need to be
+/// hdr.end(window_width, window_height, delta_time);
+/// ```
+pub struct HdrTarget {
+    /// the scene is rendered into this one
+    scene: Framebuffer,
+    /// log luminance of the scene, averaged by its mipmaps
+    luminance: Framebuffer,
+    /// a tone mapping program for each operator, the shader selects it at compile time
+    tonemap: Vec<(ToneMapping, Shader)>,
+    luminance_shader: Shader,
+    quad_vao: u32,
+    quad_vbo: u32,
+    pub tone_mapping: ToneMapping,
+    /// scales the colors before tone mapping
+    pub exposure: f32,
+    /// sets `exposure` from the average luminance every frame
+    pub auto_exposure: bool,
+    /// the luminance auto exposure maps the average to, middle gray by default;
+    /// Q and E adjust it instead of `exposure` while auto exposure is on
+    pub key_value: f32,
+    /// how quickly auto exposure adapts, like eyes getting used to the dark; 1/s
+    pub adaptation_rate: f32,
+    average_luminance: f32,
+}
+
+impl HdrTarget {
+    /// For a window of the given framebuffer size
+    pub unsafe fn new(width: i32, height: i32) -> Result<HdrTarget, FramebufferError> {
+        let scene =
+            Framebuffer::builder(width, height).color_texture(gl::RGBA16F).depth_stencil_renderbuffer().build()?;
+        let luminance = Framebuffer::builder(LUMINANCE_SIZE, LUMINANCE_SIZE).color_texture(gl::R16F).build()?;
+
+        let tonemap = ToneMapping::ALL
+            .iter()
+            .map(|&tone_mapping| {
+                let defines = [tone_mapping.define(), ("GAMMA", "2.2")];
+                let shader = Shader::with_defines(
+                    "src/shared/shaders/post_process.vsh",
+                    "src/shared/shaders/tonemap.fsh",
+                    &defines,
+                );
+                shader.use_program();
+                shader.set("screenTexture", &0);
+                (tone_mapping, shader)
+            })
+            .collect();
+        let luminance_shader =
+            Shader::new("src/shared/shaders/post_process.vsh", "src/shared/shaders/hdr_luminance.fsh");
+        luminance_shader.use_program();
+        luminance_shader.set("hdrBuffer", &0);
+        let (quad_vao, quad_vbo) = create_quad();
+        Ok(HdrTarget {
+            scene,
+            luminance,
+            tonemap,
+            luminance_shader,
+            quad_vao,
+            quad_vbo,
+            tone_mapping: ToneMapping::Reinhard,
+            exposure: 1.0,
+            auto_exposure: false,
+            key_value: 0.18,
+            adaptation_rate: 1.5,
+            average_luminance: 1.0,
+        })
+    }
+
+    /// Resizes the scene target along with the window
+    pub unsafe fn handle_event(&mut self, event: &glfw::WindowEvent) -> Result<(), FramebufferError> {
+        self.scene.handle_event(event)
+    }
+
+    /// Holding E brightens the image and Q darkens it, by scaling `exposure`,
+    /// or `key_value` with auto exposure on
+    pub fn process_input(&mut self, window: &glfw::Window, delta_time: f32) {
+        let target = if self.auto_exposure { &mut self.key_value } else { &mut self.exposure };
+        if window.get_key(Key::E) == Action::Press {
+            *target *= 1.0 + delta_time;
+        }
+        if window.get_key(Key::Q) == Action::Press {
+            *target /= 1.0 + delta_time;
+        }
+    }
+
+    /// The geometric mean of the scene's luminance, as of the last frame
+    /// with auto exposure
+    pub fn average_luminance(&self) -> f32 {
+        self.average_luminance
+    }
+
+    pub fn color_texture(&self) -> u32 {
+        self.scene.color_texture(0)
+    }
+
+    /// Binds the floating point framebuffer the scene has to be rendered into
+    pub unsafe fn begin(&self) {
+        self.scene.bind();
+    }
+
+    /// Tone maps the rendered scene into the default framebuffer of the given
+    /// size, which is bound afterwards. `delta_time` is the time since the
+    /// last frame, for auto exposure to adapt gradually.
+    pub unsafe fn end(&mut self, width: i32, height: i32, delta_time: f32) {
+        gl::Disable(gl::DEPTH_TEST);
+        gl::ActiveTexture(gl::TEXTURE0);
+        gl::BindVertexArray(self.quad_vao);
+
+        if self.auto_exposure {
+            self.adapt_exposure(delta_time);
+        }
+
+        Framebuffer::bind_default(width, height);
+        let (_, tonemap) = self.tonemap.iter().find(|(tone_mapping, _)| *tone_mapping == self.tone_mapping).unwrap();
+        tonemap.use_program();
+        tonemap.set("exposure", &self.exposure);
+        gl::BindTexture(gl::TEXTURE_2D, self.scene.color_texture(0));
+        gl::DrawArrays(gl::TRIANGLES, 0, 6);
+
+        gl::BindVertexArray(0);
+        gl::Enable(gl::DEPTH_TEST);
+    }
+
+    /// Averages the scene's luminance down to one texel with mipmaps and
+    /// moves `exposure` towards mapping it to `key_value`. Reading the texel
+    /// back waits for the frame so far to finish, but it's a single float.
+    unsafe fn adapt_exposure(&mut self, delta_time: f32) {
+        self.luminance.bind();
+        self.luminance_shader.use_program();
+        gl::BindTexture(gl::TEXTURE_2D, self.scene.color_texture(0));
+        gl::DrawArrays(gl::TRIANGLES, 0, 6);
+
+        let mut log_average = 0.0f32;
+        gl::BindTexture(gl::TEXTURE_2D, self.luminance.color_texture(0));
+        gl::GenerateMipmap(gl::TEXTURE_2D);
+        gl::GetTexImage(
+            gl::TEXTURE_2D,
+            LUMINANCE_AVERAGE_LEVEL,
+            gl::RED,
+            gl::FLOAT,
+            &mut log_average as *mut f32 as *mut c_void,
+        );
+        self.average_luminance = log_average.exp();
+
+        let target = (self.key_value / self.average_luminance).clamp(MIN_EXPOSURE, MAX_EXPOSURE);
+        // exponential decay towards the target, independent of the frame rate
+        let blend = 1.0 - (-delta_time * self.adaptation_rate).exp();
+        self.exposure += (target - self.exposure) * blend;
+    }
+}
+
+impl Drop for HdrTarget {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteVertexArrays(1, &self.quad_vao);
+            gl::DeleteBuffers(1, &self.quad_vbo);
+        }
+    }
+}
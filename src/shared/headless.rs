@@ -0,0 +1,154 @@
+#![allow(dead_code)]
+
+use std::cell::{Cell, RefCell};
+use std::env;
+use std::ffi::CStr;
+use std::os::raw::c_void;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Receiver;
+
+use glfw::Context;
+use image::RgbImage;
+
+/// Fixed time step used to advance the clock between headless frames, so that
+/// animated tutorials render the same frames on every run
+const HEADLESS_TIME_STEP: f64 = 1.0 / 60.0;
+
+#[derive(Clone, Copy)]
+struct Headless {
+    frames: u32,
+    rendered: u32,
+}
+
+thread_local! {
+    /// Frame budget in headless mode, `None` when rendering to a visible window
+    /// until the user closes it
+    static HEADLESS: Cell<Option<Headless>> = Cell::new(None);
+    /// Where to save the last headless frame, if anywhere
+    static CAPTURE_PATH: RefCell<Option<PathBuf>> = RefCell::new(None);
+}
+
+/// Switch to headless mode: windows created afterwards are invisible and the
+/// render loop exits after `frames` frames
+pub fn set_headless(frames: u32) {
+    HEADLESS.with(|h| h.set(Some(Headless { frames, rendered: 0 })));
+}
+
+pub fn is_headless() -> bool {
+    HEADLESS.with(|h| h.get().is_some())
+}
+
+/// Save the last frame rendered in headless mode as a PNG image at `path`
+pub fn set_capture_path<P: AsRef<Path>>(path: P) {
+    CAPTURE_PATH.with(|c| *c.borrow_mut() = Some(path.as_ref().to_path_buf()));
+}
+
+/// glfw window creation as used by every tutorial. In headless mode the window
+/// is never shown, so its back buffer acts as an offscreen framebuffer which a
+/// software GL (e.g. Mesa llvmpipe) can render into without a display.
+///
+/// The context creation API can be picked with `GFX_RS_CONTEXT_API=egl|osmesa`
+/// for build machines without an X server.
+pub fn create_window(
+    glfw: &mut glfw::Glfw,
+    width: u32,
+    height: u32,
+    title: &str,
+) -> (glfw::Window, Receiver<(f64, glfw::WindowEvent)>) {
+    if is_headless() {
+        glfw.window_hint(glfw::WindowHint::Visible(false));
+        match env::var("GFX_RS_CONTEXT_API").as_ref().map(String::as_str) {
+            Ok("egl") => glfw.window_hint(glfw::WindowHint::ContextCreationApi(glfw::ContextCreationApi::Egl)),
+            Ok("osmesa") => glfw.window_hint(glfw::WindowHint::ContextCreationApi(glfw::ContextCreationApi::OsMesa)),
+            _ => {}
+        }
+    }
+
+    glfw.create_window(width, height, title, glfw::WindowMode::Windowed).expect("Failed to create GLFW window")
+}
+
+/// `create_window` with a multisampled back buffer of `samples` samples per
+/// pixel for MSAA, see 4.11 Anti Aliasing. Multisampling also has to be
+/// enabled with `gl::Enable(gl::MULTISAMPLE)`, although most drivers do so
+/// by default.
+pub fn create_window_with_samples(
+    glfw: &mut glfw::Glfw,
+    width: u32,
+    height: u32,
+    title: &str,
+    samples: u32,
+) -> (glfw::Window, Receiver<(f64, glfw::WindowEvent)>) {
+    glfw.window_hint(glfw::WindowHint::Samples(Some(samples)));
+    let window = create_window(glfw, width, height, title);
+    // don't leak into windows created later on
+    glfw.window_hint(glfw::WindowHint::Samples(None));
+    window
+}
+
+/// Render loop condition replacing `window.should_close()`. In headless mode
+/// it counts the rendered frames and pins the clock to a multiple of a fixed
+/// time step instead of waiting for the window to be closed. Once all frames
+/// are rendered the last one is saved if a capture path was set.
+pub fn window_should_close(glfw: &mut glfw::Glfw, window: &glfw::Window) -> bool {
+    HEADLESS.with(|headless| match headless.get() {
+        None => window.should_close(),
+        Some(Headless { frames, rendered }) if rendered >= frames => {
+            if let Some(path) = CAPTURE_PATH.with(|c| c.borrow_mut().take()) {
+                let (width, height) = window.get_framebuffer_size();
+                let frame = unsafe { read_front_buffer(width as u32, height as u32) };
+                frame.save(&path).unwrap_or_else(|e| panic!("Failed to save {}: {}", path.display(), e));
+            }
+            true
+        }
+        Some(Headless { frames, rendered }) => {
+            glfw.set_time(rendered as f64 * HEADLESS_TIME_STEP);
+            headless.set(Some(Headless { frames, rendered: rendered + 1 }));
+            false
+        }
+    })
+}
+
+/// Replaces `glfw.get_time()` in the tutorials. In headless mode this is the
+/// pinned time of the current frame: glfw's clock keeps running after
+/// `set_time`, which would make animated frames differ from run to run.
+pub fn get_time(glfw: &glfw::Glfw) -> f64 {
+    HEADLESS.with(|headless| match headless.get() {
+        Some(Headless { rendered, .. }) => rendered.saturating_sub(1) as f64 * HEADLESS_TIME_STEP,
+        None => glfw.get_time(),
+    })
+}
+
+/// `GL_RENDERER` and `GL_VERSION` of the context a headless tutorial gets, one
+/// per line. Doubles as a probe for test runners: panics if glfw can't be
+/// initialized or no context can be created.
+pub fn gl_renderer() -> String {
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+    glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    #[cfg(target_os = "macos")]
+    glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+    if !is_headless() {
+        set_headless(0);
+    }
+
+    let (mut window, _events) = create_window(&mut glfw, 1, 1, "gl-info");
+    window.make_current();
+    gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+    let string = |name| unsafe { CStr::from_ptr(gl::GetString(name) as *const _).to_string_lossy().into_owned() };
+    format!("{}\n{}", string(gl::RENDERER), string(gl::VERSION))
+}
+
+/// Reads back the frame presented by the last `swap_buffers` call. The window
+/// is invisible, so the front buffer is never overwritten by the compositor.
+unsafe fn read_front_buffer(width: u32, height: u32) -> RgbImage {
+    let mut pixels = vec![0u8; (width * height * 3) as usize];
+    gl::BindFramebuffer(gl::READ_FRAMEBUFFER, 0);
+    gl::ReadBuffer(gl::FRONT);
+    gl::PixelStorei(gl::PACK_ALIGNMENT, 1);
+    gl::ReadPixels(0, 0, width as i32, height as i32, gl::RGB, gl::UNSIGNED_BYTE, pixels.as_mut_ptr() as *mut c_void);
+
+    // OpenGL's origin is the bottom left corner, image rows start at the top
+    let frame = RgbImage::from_raw(width, height, pixels).unwrap();
+    image::imageops::flip_vertical(&frame)
+}
@@ -10,10 +10,107 @@ macro_rules! c_str {
 }
 
 /// Get offset to struct member, similar to `offset_of` in C/C++
-/// From https://stackoverflow.com/questions/40310483/how-to-get-pointer-offset-in-bytes/40310851#40310851
+/// Measured on an uninitialized value instead of a null pointer, which debug
+/// builds of newer compilers catch as a null dereference
 #[macro_export]
 macro_rules! offset_of {
-    ($ty:ty, $field:ident) => {
-        &(*(ptr::null() as *const $ty)).$field as *const _ as usize
+    ($ty:ty, $field:ident) => {{
+        let value = std::mem::MaybeUninit::<$ty>::uninit();
+        let base = value.as_ptr();
+        std::ptr::addr_of!((*base).$field) as usize - base as usize
+    }};
+}
+
+/// Fails to compile if the constant expression is false
+#[macro_export]
+macro_rules! const_assert {
+    ($condition:expr) => {
+        const _: [(); 0 - !($condition) as usize] = [];
+    };
+}
+
+/// Declares a struct that can be put into `layout (std140)` uniform blocks and
+/// `layout (std430)` storage blocks. Implements `Std140` and `Std430` for it,
+/// so it can be written to a byte buffer with the right padding, and adds
+/// `STD140_OFFSETS`/`STD430_OFFSETS` listing the byte offset of every field.
+/// All field types have to implement both traits.
+///
+/// ```ignore
+/// glsl_struct! {
+///     pub struct Matrices {
+///         pub projection: Matrix4<f32>,
+///         pub view: Matrix4<f32>,
+///     }
+/// }
+/// const_assert!(Matrices::STD140_OFFSETS[1].1 == 64);
+/// ```
+#[macro_export]
+macro_rules! glsl_struct {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_attr:meta])* $field_vis:vis $field:ident: $ty:ty),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis struct $name {
+            $($(#[$field_attr])* $field_vis $field: $ty),*
+        }
+
+        #[allow(dead_code)]
+        impl $name {
+            const GLSL_FIELD_NAMES: &'static [&'static str] = &[$(stringify!($field)),*];
+            const STD140_MEMBERS: &'static [(usize, usize)] = &[
+                $((<$ty as $crate::shared::Std140>::ALIGNMENT, <$ty as $crate::shared::Std140>::SIZE)),*
+            ];
+            const STD430_MEMBERS: &'static [(usize, usize)] = &[
+                $((<$ty as $crate::shared::Std430>::ALIGNMENT, <$ty as $crate::shared::Std430>::SIZE)),*
+            ];
+
+            /// `(field, byte offset)` in std140 layout, in declaration order
+            pub const STD140_OFFSETS: &'static [(&'static str, usize)] = &[$((
+                stringify!($field),
+                $crate::shared::member_offset(
+                    Self::STD140_MEMBERS,
+                    $crate::shared::field_index(Self::GLSL_FIELD_NAMES, stringify!($field)),
+                ),
+            )),*];
+
+            /// `(field, byte offset)` in std430 layout, in declaration order
+            pub const STD430_OFFSETS: &'static [(&'static str, usize)] = &[$((
+                stringify!($field),
+                $crate::shared::member_offset(
+                    Self::STD430_MEMBERS,
+                    $crate::shared::field_index(Self::GLSL_FIELD_NAMES, stringify!($field)),
+                ),
+            )),*];
+        }
+
+        // structs are padded to a multiple of 16 bytes in std140 only
+        impl $crate::shared::Std140 for $name {
+            const ALIGNMENT: usize = $crate::shared::align_to($crate::shared::max_alignment(Self::STD140_MEMBERS), 16);
+            const SIZE: usize = $crate::shared::align_to(
+                $crate::shared::members_end(Self::STD140_MEMBERS),
+                <Self as $crate::shared::Std140>::ALIGNMENT,
+            );
+
+            fn write_std140(&self, out: &mut [u8]) {
+                let mut writer = $crate::shared::Std140Writer::new(out);
+                $(writer.write(&self.$field);)*
+            }
+        }
+
+        impl $crate::shared::Std430 for $name {
+            const ALIGNMENT: usize = $crate::shared::max_alignment(Self::STD430_MEMBERS);
+            const SIZE: usize = $crate::shared::align_to(
+                $crate::shared::members_end(Self::STD430_MEMBERS),
+                <Self as $crate::shared::Std430>::ALIGNMENT,
+            );
+
+            fn write_std430(&self, out: &mut [u8]) {
+                let mut writer = $crate::shared::Std430Writer::new(out);
+                $(writer.write(&self.$field);)*
+            }
+        }
     };
 }
@@ -1,14 +1,14 @@
 #![allow(dead_code)]
 
-use std::ffi::CString;
 use std::mem::size_of;
 use std::os::raw::c_void;
 use std::ptr;
 
 use cgmath::prelude::*;
 use cgmath::{Vector2, Vector3};
+use gl::types::GLenum;
 
-use crate::shared::Shader;
+use crate::shared::{gl_type_name, Buffer, Shader};
 
 // NOTE: without repr(C) the compiler may reorder the fields or use different padding/alignment than C.
 // Depending on how you pass the data to OpenGL, this may be bad. In this case it's not strictly
@@ -27,6 +27,18 @@ pub struct Vertex {
     pub bitangent: Vector3<f32>,
 }
 
+impl Vertex {
+    /// attribute locations and GLSL types as set up in `Mesh::setup_mesh`, see
+    /// `Shader::check_vertex_layout`
+    pub const LAYOUT: &'static [(u32, GLenum)] = &[
+        (0, gl::FLOAT_VEC3), // position
+        (1, gl::FLOAT_VEC3), // normal
+        (2, gl::FLOAT_VEC2), // texCoords
+        (3, gl::FLOAT_VEC3), // tangent
+        (4, gl::FLOAT_VEC3), // bitangent
+    ];
+}
+
 impl Default for Vertex {
     fn default() -> Self {
         Vertex {
@@ -39,6 +51,63 @@ impl Default for Vertex {
     }
 }
 
+/// Attribute locations of the `Vertex` fields, `None` for fields the shader
+/// doesn't use. Defaults to `Vertex::LAYOUT`, see `Mesh::set_vertex_locations`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VertexLocations {
+    pub position: Option<u32>,
+    pub normal: Option<u32>,
+    pub tex_coords: Option<u32>,
+    pub tangent: Option<u32>,
+    pub bitangent: Option<u32>,
+}
+
+impl Default for VertexLocations {
+    fn default() -> Self {
+        VertexLocations {
+            position: Some(0),
+            normal: Some(1),
+            tex_coords: Some(2),
+            tangent: Some(3),
+            bitangent: Some(4),
+        }
+    }
+}
+
+impl VertexLocations {
+    /// Without tangent and bitangent, leaving locations 3 and up free, e.g.
+    /// for an instanced `mat4` as in 4.10 Instancing
+    pub fn without_tangents() -> Self {
+        VertexLocations { tangent: None, bitangent: None, ..VertexLocations::default() }
+    }
+
+    /// like `Vertex::LAYOUT`, for the enabled fields
+    pub fn layout(&self) -> Vec<(u32, GLenum)> {
+        let fields = [
+            (self.position, gl::FLOAT_VEC3),
+            (self.normal, gl::FLOAT_VEC3),
+            (self.tex_coords, gl::FLOAT_VEC2),
+            (self.tangent, gl::FLOAT_VEC3),
+            (self.bitangent, gl::FLOAT_VEC3),
+        ];
+        fields.iter().filter_map(|(location, gl_type)| location.map(|location| (location, *gl_type))).collect()
+    }
+}
+
+/// Columns and components per column of the GLSL types that can be used as
+/// per-instance attributes
+fn attribute_shape(gl_type: GLenum) -> Option<(u32, i32)> {
+    match gl_type {
+        gl::FLOAT => Some((1, 1)),
+        gl::FLOAT_VEC2 => Some((1, 2)),
+        gl::FLOAT_VEC3 => Some((1, 3)),
+        gl::FLOAT_VEC4 => Some((1, 4)),
+        gl::FLOAT_MAT3 => Some((3, 3)),
+        gl::FLOAT_MAT4 => Some((4, 4)),
+        _ => None,
+    }
+}
+
 #[derive(Clone)]
 pub struct Texture {
     pub id: u32,
@@ -56,11 +125,23 @@ pub struct Mesh {
     /* Render data */
     vbo: u32,
     ebo: u32,
+    locations: VertexLocations,
+    /// per-instance attributes set with `set_instance_attribute`
+    instance_layout: Vec<(u32, GLenum)>,
 }
 
 impl Mesh {
     pub fn new(vertices: Vec<Vertex>, indices: Vec<u32>, textures: Vec<Texture>) -> Mesh {
-        let mut mesh = Mesh { vertices, indices, textures, vao: 0, vbo: 0, ebo: 0 };
+        let mut mesh = Mesh {
+            vertices,
+            indices,
+            textures,
+            vao: 0,
+            vbo: 0,
+            ebo: 0,
+            locations: VertexLocations::default(),
+            instance_layout: Vec::new(),
+        };
 
         // now that we have all the required data, set the vertex buffers and its attribute pointers.
         unsafe { mesh.setup_mesh() }
@@ -69,6 +150,97 @@ impl Mesh {
 
     /// render the mesh
     pub unsafe fn draw(&self, shader: &Shader) {
+        self.bind_textures(shader);
+
+        // draw mesh
+        gl::BindVertexArray(self.vao);
+        gl::DrawElements(gl::TRIANGLES, self.indices.len() as i32, gl::UNSIGNED_INT, ptr::null());
+        gl::BindVertexArray(0);
+
+        // always good practice to set everything back to defaults once configured.
+        gl::ActiveTexture(gl::TEXTURE0);
+    }
+
+    /// render `count` instances of the mesh in one draw call, see `set_instance_attribute`
+    pub unsafe fn draw_instanced(&self, shader: &Shader, count: usize) {
+        self.bind_textures(shader);
+
+        gl::BindVertexArray(self.vao);
+        gl::DrawElementsInstanced(
+            gl::TRIANGLES,
+            self.indices.len() as i32,
+            gl::UNSIGNED_INT,
+            ptr::null(),
+            count as i32,
+        );
+        gl::BindVertexArray(0);
+
+        gl::ActiveTexture(gl::TEXTURE0);
+    }
+
+    /// Moves the vertex attributes to other locations or disables them, e.g.
+    /// to free locations for per-instance attributes
+    pub unsafe fn set_vertex_locations(&mut self, locations: VertexLocations) {
+        gl::BindVertexArray(self.vao);
+        for (location, _) in self.locations.layout() {
+            gl::DisableVertexAttribArray(location);
+        }
+        self.locations = locations;
+        gl::BindBuffer(gl::ARRAY_BUFFER, self.vbo);
+        self.configure_vertex_attributes();
+        gl::BindVertexArray(0);
+    }
+
+    /// Feeds a per-instance attribute of GLSL type `gl_type` at `location` from
+    /// `buffer`, advancing every `divisor` instances. Matrices take one location
+    /// per column, e.g. a `mat4` at location 3 takes 3 to 6, so they must not
+    /// overlap the vertex attributes, see `set_vertex_locations`.
+    pub unsafe fn set_instance_attribute<T: Copy>(
+        &mut self,
+        buffer: &Buffer<T>,
+        location: u32,
+        gl_type: GLenum,
+        divisor: u32,
+    ) {
+        let (columns, components) = attribute_shape(gl_type)
+            .unwrap_or_else(|| panic!("unsupported instance attribute type {}", gl_type_name(gl_type)));
+        assert_eq!(
+            size_of::<T>(),
+            columns as usize * components as usize * size_of::<f32>(),
+            "instance data doesn't match a {}",
+            gl_type_name(gl_type)
+        );
+        if let Some((taken, _)) =
+            self.locations.layout().into_iter().find(|(taken, _)| (location..location + columns).contains(taken))
+        {
+            panic!(
+                "instance attribute at location {} overlaps vertex attribute location {}, see Mesh::set_vertex_locations",
+                location, taken
+            );
+        }
+
+        gl::BindVertexArray(self.vao);
+        gl::BindBuffer(gl::ARRAY_BUFFER, buffer.id);
+        let column_size = components as usize * size_of::<f32>();
+        for column in 0..columns {
+            gl::EnableVertexAttribArray(location + column);
+            gl::VertexAttribPointer(
+                location + column,
+                components,
+                gl::FLOAT,
+                gl::FALSE,
+                size_of::<T>() as i32,
+                (column as usize * column_size) as *const c_void,
+            );
+            gl::VertexAttribDivisor(location + column, divisor);
+        }
+        gl::BindVertexArray(0);
+
+        self.instance_layout.retain(|(taken, _)| *taken != location);
+        self.instance_layout.push((location, gl_type));
+    }
+
+    unsafe fn bind_textures(&self, shader: &Shader) {
         // bind appropriate textures
         let mut diffuse_nr = 0;
         let mut specular_nr = 0;
@@ -98,19 +270,23 @@ impl Mesh {
                 _ => panic!("unknown texture type"),
             };
             // now set the sampler to the correct texture unit
-            let sampler = CString::new(format!("{}{}", name, number)).unwrap();
-            gl::Uniform1i(gl::GetUniformLocation(shader.id, sampler.as_ptr()), i as i32);
+            shader.try_set(&format!("{}{}", name, number), &(i as i32));
             // and finally bind the texture
             gl::BindTexture(gl::TEXTURE_2D, texture.id);
         }
+    }
 
-        // draw mesh
-        gl::BindVertexArray(self.vao);
-        gl::DrawElements(gl::TRIANGLES, self.indices.len() as i32, gl::UNSIGNED_INT, ptr::null());
-        gl::BindVertexArray(0);
-
-        // always good practice to set everything back to defaults once configured.
-        gl::ActiveTexture(gl::TEXTURE0);
+    /// Problems with drawing this mesh with `shader`: vertex inputs the mesh
+    /// doesn't provide and samplers none of its textures are bound to.
+    /// Meant to be called once after `draw`, so the samplers have been set.
+    pub unsafe fn check_shader(&self, shader: &Shader) -> Vec<String> {
+        let mut layout = self.locations.layout();
+        layout.extend_from_slice(&self.instance_layout);
+        let mut problems = shader.check_vertex_layout(&layout);
+        for sampler in shader.unset_samplers() {
+            problems.push(format!("sampler '{}' is not set, no texture of the mesh matches it", sampler.name));
+        }
+        problems
     }
 
     unsafe fn setup_mesh(&mut self) {
@@ -134,24 +310,44 @@ impl Mesh {
         let data = &self.indices[0] as *const u32 as *const c_void;
         gl::BufferData(gl::ELEMENT_ARRAY_BUFFER, size, data, gl::STATIC_DRAW);
 
-        // set the vertex attribute pointers
+        self.configure_vertex_attributes();
+
+        gl::BindVertexArray(0);
+    }
+
+    /// sets the vertex attribute pointers, with the VAO and VBO bound
+    unsafe fn configure_vertex_attributes(&self) {
         let size = size_of::<Vertex>() as i32;
+        let locations = &self.locations;
         // vertex positions
-        gl::EnableVertexAttribArray(0);
-        gl::VertexAttribPointer(0, 3, gl::FLOAT, gl::FALSE, size, offset_of!(Vertex, position) as *const c_void);
+        if let Some(location) = locations.position {
+            gl::EnableVertexAttribArray(location);
+            let offset = offset_of!(Vertex, position) as *const c_void;
+            gl::VertexAttribPointer(location, 3, gl::FLOAT, gl::FALSE, size, offset);
+        }
         // vertex normals
-        gl::EnableVertexAttribArray(1);
-        gl::VertexAttribPointer(1, 3, gl::FLOAT, gl::FALSE, size, offset_of!(Vertex, normal) as *const c_void);
+        if let Some(location) = locations.normal {
+            gl::EnableVertexAttribArray(location);
+            let offset = offset_of!(Vertex, normal) as *const c_void;
+            gl::VertexAttribPointer(location, 3, gl::FLOAT, gl::FALSE, size, offset);
+        }
         // vertex texture coords
-        gl::EnableVertexAttribArray(2);
-        gl::VertexAttribPointer(2, 2, gl::FLOAT, gl::FALSE, size, offset_of!(Vertex, tex_coords) as *const c_void);
+        if let Some(location) = locations.tex_coords {
+            gl::EnableVertexAttribArray(location);
+            let offset = offset_of!(Vertex, tex_coords) as *const c_void;
+            gl::VertexAttribPointer(location, 2, gl::FLOAT, gl::FALSE, size, offset);
+        }
         // vertex tangent
-        gl::EnableVertexAttribArray(3);
-        gl::VertexAttribPointer(3, 3, gl::FLOAT, gl::FALSE, size, offset_of!(Vertex, tangent) as *const c_void);
+        if let Some(location) = locations.tangent {
+            gl::EnableVertexAttribArray(location);
+            let offset = offset_of!(Vertex, tangent) as *const c_void;
+            gl::VertexAttribPointer(location, 3, gl::FLOAT, gl::FALSE, size, offset);
+        }
         // vertex bitangent
-        gl::EnableVertexAttribArray(4);
-        gl::VertexAttribPointer(4, 3, gl::FLOAT, gl::FALSE, size, offset_of!(Vertex, bitangent) as *const c_void);
-
-        gl::BindVertexArray(0);
+        if let Some(location) = locations.bitangent {
+            gl::EnableVertexAttribArray(location);
+            let offset = offset_of!(Vertex, bitangent) as *const c_void;
+            gl::VertexAttribPointer(location, 3, gl::FLOAT, gl::FALSE, size, offset);
+        }
     }
 }
@@ -6,7 +6,9 @@ use std::path::Path;
 use cgmath::{vec2, vec3};
 use image::GenericImageView;
 
-use crate::shared::{image_format, Mesh, Shader, Texture, Vertex};
+use gl::types::GLenum;
+
+use crate::shared::{compute_tangents, image_format, Buffer, Mesh, Shader, Texture, Vertex, VertexLocations};
 
 #[derive(Default)]
 pub struct Model {
@@ -15,6 +17,7 @@ pub struct Model {
     pub textures_loaded: Vec<Texture>,
     // stores all the textures loaded so far, optimization to make sure textures aren't laoded more than once.
     directory: String,
+    height_maps: bool,
 }
 
 impl Model {
@@ -25,6 +28,16 @@ impl Model {
         model
     }
 
+    /// Like `new`, but loads the materials' map_Ka textures as height maps
+    /// (`texture_height`), as models exported for LearnOpenGL store them there.
+    /// Most models use map_Ka for ambient or reflection maps instead, e.g.
+    /// nanosuit.mtl, so `new` leaves it alone.
+    pub fn with_height_maps(path: &str) -> Model {
+        let mut model = Model { height_maps: true, ..Model::default() };
+        model.load_model(path);
+        model
+    }
+
     pub fn draw(&self, shader: &Shader) {
         for mesh in &self.meshes {
             unsafe {
@@ -33,6 +46,46 @@ impl Model {
         }
     }
 
+    /// render `count` instances of every mesh, see `Mesh::draw_instanced`
+    pub fn draw_instanced(&self, shader: &Shader, count: usize) {
+        for mesh in &self.meshes {
+            unsafe {
+                mesh.draw_instanced(shader, count);
+            }
+        }
+    }
+
+    /// `Mesh::set_vertex_locations` for all meshes
+    pub unsafe fn set_vertex_locations(&mut self, locations: VertexLocations) {
+        for mesh in &mut self.meshes {
+            mesh.set_vertex_locations(locations);
+        }
+    }
+
+    /// `Mesh::set_instance_attribute` for all meshes, which share `buffer`
+    pub unsafe fn set_instance_attribute<T: Copy>(
+        &mut self,
+        buffer: &Buffer<T>,
+        location: u32,
+        gl_type: GLenum,
+        divisor: u32,
+    ) {
+        for mesh in &mut self.meshes {
+            mesh.set_instance_attribute(buffer, location, gl_type, divisor);
+        }
+    }
+
+    /// `Mesh::check_shader` for all meshes, without duplicates
+    pub unsafe fn check_shader(&self, shader: &Shader) -> Vec<String> {
+        let mut problems: Vec<String> = Vec::new();
+        for problem in self.meshes.iter().flat_map(|mesh| mesh.check_shader(shader)) {
+            if !problems.contains(&problem) {
+                problems.push(problem);
+            }
+        }
+        problems
+    }
+
     // loads a model from file and stores the resulting meshes in the meshes vector.
     fn load_model(&mut self, path: &str) {
         let path = Path::new(path);
@@ -59,28 +112,17 @@ impl Model {
                     ..Vertex::default()
                 })
             }
+            // normal maps need the tangent space, which the obj format doesn't store
+            if !t.is_empty() {
+                compute_tangents(&mut vertices, &indices);
+            }
 
             // process material
             let mut textures = Vec::new();
             if let Some(material_id) = mesh.material_id {
-                let material = &materials[material_id];
-
-                // 1. diffuse map
-                if !material.diffuse_texture.is_empty() {
-                    let texture = self.load_material_texture(&material.diffuse_texture, "texture_diffuse");
-                    textures.push(texture);
-                }
-                // 2. specular map
-                if !material.specular_texture.is_empty() {
-                    let texture = self.load_material_texture(&material.specular_texture, "texture_specular");
-                    textures.push(texture);
+                for (path, type_name) in material_textures(&materials[material_id], self.height_maps) {
+                    textures.push(self.load_material_texture(path, type_name));
                 }
-                // 3. normal map
-                if !material.normal_texture.is_empty() {
-                    let texture = self.load_material_texture(&material.normal_texture, "texture_normal");
-                    textures.push(texture);
-                }
-                // NOTE: no height maps
             }
 
             self.meshes.push(Mesh::new(vertices, indices, textures));
@@ -105,6 +147,25 @@ impl Model {
     }
 }
 
+/// The texture files of `material` with the sampler type `Mesh::draw` binds
+/// them to, see `Model::with_height_maps` for `height_maps`
+fn material_textures(material: &tobj::Material, height_maps: bool) -> Vec<(&str, &'static str)> {
+    let mut textures = vec![
+        // 1. diffuse map
+        (material.diffuse_texture.as_str(), "texture_diffuse"),
+        // 2. specular map
+        (material.specular_texture.as_str(), "texture_specular"),
+        // 3. normal map
+        (material.normal_texture.as_str(), "texture_normal"),
+    ];
+    // 4. height map: map_Bump is taken by the normal map
+    if height_maps {
+        textures.push((material.ambient_texture.as_str(), "texture_height"));
+    }
+    textures.retain(|(path, _)| !path.is_empty());
+    textures
+}
+
 unsafe fn texture_from_file(path: &str, directory: &str) -> u32 {
     let filename = format!("{}/{}", directory, path);
 
@@ -138,3 +199,53 @@ unsafe fn texture_from_file(path: &str, directory: &str) -> u32 {
 
     texture_id
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufReader;
+
+    // the first material of nanosuit.mtl
+    const MTL: &str = "newmtl Arm
+Ns 96.078431
+Kd 0.640000 0.640000 0.640000
+map_Bump arm_showroom_ddn.png
+map_Ka arm_showroom_refl.png
+map_Kd arm_dif.png
+map_Ks arm_showroom_spec.png
+";
+
+    fn material() -> tobj::Material {
+        let (mut materials, _) = tobj::load_mtl_buf(&mut BufReader::new(MTL.as_bytes())).unwrap();
+        materials.remove(0)
+    }
+
+    #[test]
+    fn material_textures_leave_out_map_ka() {
+        assert_eq!(
+            material_textures(&material(), false),
+            [
+                ("arm_dif.png", "texture_diffuse"),
+                ("arm_showroom_spec.png", "texture_specular"),
+                ("arm_showroom_ddn.png", "texture_normal"),
+            ]
+        );
+    }
+
+    #[test]
+    fn material_textures_take_map_ka_as_height_map_when_asked() {
+        let material = material();
+        let textures = material_textures(&material, true);
+        assert_eq!(textures.len(), 4);
+        assert_eq!(textures[3], ("arm_showroom_refl.png", "texture_height"));
+    }
+
+    #[test]
+    fn material_textures_skip_missing_maps() {
+        let mut material = material();
+        material.specular_texture.clear();
+        material.ambient_texture.clear();
+        let types: Vec<_> = material_textures(&material, true).into_iter().map(|(_, type_name)| type_name).collect();
+        assert_eq!(types, ["texture_diffuse", "texture_normal"]);
+    }
+}
@@ -0,0 +1,179 @@
+#![allow(dead_code)]
+
+use std::ptr;
+
+use cgmath::{perspective, Deg, Matrix4, Point3, SquareMatrix, Vector3};
+use gl;
+
+use crate::shared::{Framebuffer, FramebufferError, Shader};
+
+/// Look direction and up vector of the cubemap faces, in the order of the
+/// `TEXTURE_CUBE_MAP_POSITIVE_X` .. `NEGATIVE_Z` targets
+const CUBE_FACE_VIEWS: [([f32; 3], [f32; 3]); 6] = [
+    ([1.0, 0.0, 0.0], [0.0, -1.0, 0.0]),
+    ([-1.0, 0.0, 0.0], [0.0, -1.0, 0.0]),
+    ([0.0, 1.0, 0.0], [0.0, 0.0, 1.0]),
+    ([0.0, -1.0, 0.0], [0.0, 0.0, -1.0]),
+    ([0.0, 0.0, 1.0], [0.0, -1.0, 0.0]),
+    ([0.0, 0.0, -1.0], [0.0, -1.0, 0.0]),
+];
+
+/// Shadows of a point light, which shines in all directions: the distances
+/// of the nearest surfaces around the light are rendered into a depth
+/// cubemap, all six faces in one pass by a geometry shader. Used like
+/// `ShadowMap`, with a lit pass including src/shared/shaders/point_shadow.glsl.
+///
+/// ```ignore
+/// let depth_shader = point_shadow.begin();
+/// // draw the shadow casters, setting `model` on depth_shader
+/// point_shadow.end(window_width, window_height);
+/// point_shadow.apply(&shader, 1);
+/// ```
+pub struct PointShadow {
+    fbo: u32,
+    depth_cubemap: u32,
+    size: i32,
+    /// renders positions at location 0 with the `model` matrix into the cubemap
+    pub depth_shader: Shader,
+    light_position: Point3<f32>,
+    near: f32,
+    far: f32,
+    /// subtracted from the distance to the light against shadow acne
+    pub bias: f32,
+    /// soft shadows by sampling a disk around the lookup direction
+    pub pcf: bool,
+}
+
+impl PointShadow {
+    /// A depth cubemap with `size` x `size` faces, holding distances from
+    /// `near` to `far`. Nothing further away from the light casts shadows.
+    pub unsafe fn new(size: i32, near: f32, far: f32) -> Result<PointShadow, FramebufferError> {
+        if size <= 0 {
+            return Err(FramebufferError::EmptySize { width: size, height: size });
+        }
+
+        let mut depth_cubemap = 0;
+        gl::GenTextures(1, &mut depth_cubemap);
+        gl::BindTexture(gl::TEXTURE_CUBE_MAP, depth_cubemap);
+        for face in 0..6 {
+            gl::TexImage2D(
+                gl::TEXTURE_CUBE_MAP_POSITIVE_X + face,
+                0,
+                gl::DEPTH_COMPONENT as i32,
+                size,
+                size,
+                0,
+                gl::DEPTH_COMPONENT,
+                gl::FLOAT,
+                ptr::null(),
+            );
+        }
+        gl::TexParameteri(gl::TEXTURE_CUBE_MAP, gl::TEXTURE_MIN_FILTER, gl::NEAREST as i32);
+        gl::TexParameteri(gl::TEXTURE_CUBE_MAP, gl::TEXTURE_MAG_FILTER, gl::NEAREST as i32);
+        gl::TexParameteri(gl::TEXTURE_CUBE_MAP, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as i32);
+        gl::TexParameteri(gl::TEXTURE_CUBE_MAP, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as i32);
+        gl::TexParameteri(gl::TEXTURE_CUBE_MAP, gl::TEXTURE_WRAP_R, gl::CLAMP_TO_EDGE as i32);
+        gl::BindTexture(gl::TEXTURE_CUBE_MAP, 0);
+
+        // attach the whole cubemap as a layered depth attachment, the geometry shader picks the face with gl_Layer
+        let mut fbo = 0;
+        gl::GenFramebuffers(1, &mut fbo);
+        gl::BindFramebuffer(gl::FRAMEBUFFER, fbo);
+        gl::FramebufferTexture(gl::FRAMEBUFFER, gl::DEPTH_ATTACHMENT, depth_cubemap, 0);
+        gl::DrawBuffer(gl::NONE);
+        gl::ReadBuffer(gl::NONE);
+        let status = gl::CheckFramebufferStatus(gl::FRAMEBUFFER);
+        gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+        if status != gl::FRAMEBUFFER_COMPLETE {
+            gl::DeleteFramebuffers(1, &fbo);
+            gl::DeleteTextures(1, &depth_cubemap);
+            return Err(FramebufferError::Incomplete { status });
+        }
+
+        let depth_shader = Shader::builder()
+            .vertex("src/shared/shaders/point_shadow_depth.vsh")
+            .geometry("src/shared/shaders/point_shadow_depth.gsh")
+            .fragment("src/shared/shaders/point_shadow_depth.fsh")
+            .build();
+        Ok(PointShadow {
+            fbo,
+            depth_cubemap,
+            size,
+            depth_shader,
+            light_position: Point3::new(0.0, 0.0, 0.0),
+            near,
+            far,
+            bias: 0.15,
+            pcf: true,
+        })
+    }
+
+    pub fn set_light_position(&mut self, position: Point3<f32>) {
+        self.light_position = position;
+    }
+
+    pub fn light_position(&self) -> Point3<f32> {
+        self.light_position
+    }
+
+    pub fn far_plane(&self) -> f32 {
+        self.far
+    }
+
+    pub fn depth_cubemap(&self) -> u32 {
+        self.depth_cubemap
+    }
+
+    /// World space to the clip space of each cubemap face, as seen from the
+    /// light with a 90 degree field of view
+    pub fn shadow_matrices(&self) -> [Matrix4<f32>; 6] {
+        let projection = perspective(Deg(90.0), 1.0, self.near, self.far);
+        let mut matrices = [Matrix4::identity(); 6];
+        for (matrix, (direction, up)) in matrices.iter_mut().zip(&CUBE_FACE_VIEWS) {
+            let target = self.light_position + Vector3::from(*direction);
+            *matrix = projection * Matrix4::look_at(self.light_position, target, Vector3::from(*up));
+        }
+        matrices
+    }
+
+    /// Binds and clears the cubemap for the depth pass. Returns the depth
+    /// shader, ready to draw with after setting `model`.
+    pub unsafe fn begin(&self) -> &Shader {
+        gl::BindFramebuffer(gl::FRAMEBUFFER, self.fbo);
+        gl::Viewport(0, 0, self.size, self.size);
+        gl::Clear(gl::DEPTH_BUFFER_BIT);
+        self.depth_shader.use_program();
+        self.depth_shader.set("shadowMatrices", &self.shadow_matrices());
+        self.depth_shader.set("lightPos", &self.light_position);
+        self.depth_shader.set("farPlane", &self.far);
+        &self.depth_shader
+    }
+
+    /// Ends the depth pass, going back to the window's framebuffer of the given size
+    pub unsafe fn end(&self, width: i32, height: i32) {
+        Framebuffer::bind_default(width, height);
+    }
+
+    /// Binds the cubemap to texture unit `unit` and sets the uniforms of
+    /// point_shadow.glsl on `shader`, which is made current
+    pub unsafe fn apply(&self, shader: &Shader, unit: u32) {
+        shader.use_program();
+        shader.set("pointShadowMap", &(unit as i32));
+        shader.set("pointShadowLightPos", &self.light_position);
+        shader.set("pointShadowFarPlane", &self.far);
+        shader.set("pointShadowBias", &self.bias);
+        shader.set("pointShadowPcf", &self.pcf);
+        gl::ActiveTexture(gl::TEXTURE0 + unit);
+        gl::BindTexture(gl::TEXTURE_CUBE_MAP, self.depth_cubemap);
+        gl::ActiveTexture(gl::TEXTURE0);
+    }
+}
+
+impl Drop for PointShadow {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteFramebuffers(1, &self.fbo);
+            gl::DeleteTextures(1, &self.depth_cubemap);
+        }
+    }
+}
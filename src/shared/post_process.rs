@@ -0,0 +1,251 @@
+#![allow(dead_code)]
+
+use std::mem;
+use std::os::raw::c_void;
+use std::ptr;
+
+use gl;
+use gl::types::*;
+use glfw::{Action, Key};
+
+use crate::shared::{DepthAttachment, Framebuffer, FramebufferError, Shader, ToneMapping};
+
+const VERTEX_SHADER: &str = "src/shared/shaders/post_process.vsh";
+
+pub const SHARPEN_KERNEL: [f32; 9] = [-1.0, -1.0, -1.0, -1.0, 9.0, -1.0, -1.0, -1.0, -1.0];
+pub const BLUR_KERNEL: [f32; 9] =
+    [1.0 / 16.0, 2.0 / 16.0, 1.0 / 16.0, 2.0 / 16.0, 4.0 / 16.0, 2.0 / 16.0, 1.0 / 16.0, 2.0 / 16.0, 1.0 / 16.0];
+pub const EDGE_DETECTION_KERNEL: [f32; 9] = [1.0, 1.0, 1.0, 1.0, -8.0, 1.0, 1.0, 1.0, 1.0];
+
+/// One fullscreen pass of a `PostProcessChain`: a fragment shader reading the
+/// previous result from `screenTexture`, plus the uniforms it needs
+pub struct PostProcessPass {
+    pub name: String,
+    pub shader: Shader,
+    pub enabled: bool,
+    /// toggles the pass on and off
+    pub key: Option<Key>,
+    /// float (array) uniforms, set every time the pass runs
+    params: Vec<(String, Vec<f32>)>,
+    key_down: bool,
+}
+
+impl PostProcessPass {
+    /// Sets a `float` uniform, or a `float[]` one if `values` has more than one element
+    pub fn set_param(&mut self, name: &str, values: &[f32]) -> &mut Self {
+        match self.params.iter_mut().find(|(param, _)| param == name) {
+            Some((_, current)) => *current = values.to_vec(),
+            None => self.params.push((name.into(), values.to_vec())),
+        }
+        self
+    }
+
+    pub fn toggle_key(&mut self, key: Key) -> &mut Self {
+        self.key = Some(key);
+        self
+    }
+
+    pub fn enable(&mut self, enabled: bool) -> &mut Self {
+        self.enabled = enabled;
+        self
+    }
+}
+
+/// Renders the scene into an offscreen HDR framebuffer and then runs a list
+/// of fullscreen passes over it, ping-ponging between two more framebuffers.
+/// The last enabled pass draws to the window.
+///
+/// ```ignore
+/// chain.begin_scene();
+/// // draw the scene as usual
+/// chain.apply(window_width, window_height);
+/// ```
+pub struct PostProcessChain {
+    passes: Vec<PostProcessPass>,
+    /// the scene is rendered into this one, it's the only one with a depth buffer
+    scene: Framebuffer,
+    ping_pong: [Framebuffer; 2],
+    quad_vao: u32,
+    quad_vbo: u32,
+}
+
+impl PostProcessChain {
+    /// An empty chain for a window of the given framebuffer size
+    pub unsafe fn new(width: i32, height: i32) -> Result<PostProcessChain, FramebufferError> {
+        let target = || Framebuffer::builder(width, height).color_texture(gl::RGBA16F);
+        let scene = target().depth(DepthAttachment::DepthStencilRenderbuffer).build()?;
+        let ping_pong = [target().build()?, target().build()?];
+        let (quad_vao, quad_vbo) = create_quad();
+        Ok(PostProcessChain { passes: Vec::new(), scene, ping_pong, quad_vao, quad_vbo })
+    }
+
+    /// A chain with the built-in passes, all disabled, toggled with the number
+    /// keys: 1 grayscale, 2 inversion, 3 sharpen, 4 blur, 5 edge detection and
+    /// 6 exposure tone mapping
+    pub unsafe fn with_default_passes(width: i32, height: i32) -> Result<PostProcessChain, FramebufferError> {
+        let mut chain = PostProcessChain::new(width, height)?;
+        chain.add_pass("grayscale", "src/shared/shaders/grayscale.fsh").toggle_key(Key::Num1);
+        chain.add_pass("inversion", "src/shared/shaders/inversion.fsh").toggle_key(Key::Num2);
+        chain
+            .add_pass("sharpen", "src/shared/shaders/kernel.fsh")
+            .set_param("kernel", &SHARPEN_KERNEL)
+            .toggle_key(Key::Num3);
+        chain.add_pass("blur", "src/shared/shaders/kernel.fsh").set_param("kernel", &BLUR_KERNEL).toggle_key(Key::Num4);
+        chain
+            .add_pass("edge detection", "src/shared/shaders/kernel.fsh")
+            .set_param("kernel", &EDGE_DETECTION_KERNEL)
+            .toggle_key(Key::Num5);
+        chain
+            .add_pass_with_defines("tone mapping", "src/shared/shaders/tonemap.fsh", &[ToneMapping::Exposure.define()])
+            .set_param("exposure", &[1.0])
+            .toggle_key(Key::Num6);
+        Ok(chain)
+    }
+
+    /// Appends a disabled pass running `fragment_path`, see `PostProcessPass`
+    pub unsafe fn add_pass(&mut self, name: &str, fragment_path: &str) -> &mut PostProcessPass {
+        self.add_pass_with_defines(name, fragment_path, &[])
+    }
+
+    /// `add_pass` with `#define`s for the fragment shader, see `Shader::with_defines`
+    pub unsafe fn add_pass_with_defines(
+        &mut self,
+        name: &str,
+        fragment_path: &str,
+        defines: &[(&str, &str)],
+    ) -> &mut PostProcessPass {
+        let shader = Shader::with_defines(VERTEX_SHADER, fragment_path, defines);
+        shader.use_program();
+        shader.set("screenTexture", &0);
+        self.passes.push(PostProcessPass {
+            name: name.into(),
+            shader,
+            enabled: false,
+            key: None,
+            params: Vec::new(),
+            key_down: false,
+        });
+        self.passes.last_mut().unwrap()
+    }
+
+    pub fn pass_mut(&mut self, name: &str) -> Option<&mut PostProcessPass> {
+        self.passes.iter_mut().find(|pass| pass.name == name)
+    }
+
+    pub fn passes(&self) -> &[PostProcessPass] {
+        &self.passes
+    }
+
+    /// Toggles passes whose key was pressed since the last call; call once per frame
+    pub fn process_input(&mut self, window: &glfw::Window) {
+        for pass in &mut self.passes {
+            let key = match pass.key {
+                Some(key) => key,
+                None => continue,
+            };
+            let down = window.get_key(key) == Action::Press;
+            if down && !pass.key_down {
+                pass.enabled = !pass.enabled;
+                println!("post-processing: {} {}", pass.name, if pass.enabled { "on" } else { "off" });
+            }
+            pass.key_down = down;
+        }
+    }
+
+    /// Resizes the offscreen targets along with the window
+    pub unsafe fn handle_event(&mut self, event: &glfw::WindowEvent) -> Result<(), FramebufferError> {
+        self.scene.handle_event(event)?;
+        for target in &mut self.ping_pong {
+            target.handle_event(event)?;
+        }
+        Ok(())
+    }
+
+    /// Binds the framebuffer the scene has to be rendered into
+    pub unsafe fn begin_scene(&self) {
+        self.scene.bind();
+    }
+
+    /// Runs all enabled passes over the rendered scene, the last one drawing
+    /// into the default framebuffer of the given size
+    pub unsafe fn apply(&self, width: i32, height: i32) {
+        let enabled: Vec<_> = self.passes.iter().filter(|pass| pass.enabled).collect();
+        if enabled.is_empty() {
+            // nothing to do, just copy the scene to the window
+            self.scene.blit_to_default(width, height, gl::COLOR_BUFFER_BIT);
+            return;
+        }
+
+        gl::Disable(gl::DEPTH_TEST);
+        gl::ActiveTexture(gl::TEXTURE0);
+        gl::BindVertexArray(self.quad_vao);
+
+        let mut source = self.scene.color_texture(0);
+        for (i, pass) in enabled.iter().enumerate() {
+            if i + 1 == enabled.len() {
+                Framebuffer::bind_default(width, height);
+            } else {
+                let target = &self.ping_pong[i % 2];
+                target.bind();
+            }
+
+            pass.shader.use_program();
+            pass.shader
+                .try_set("texelSize", &cgmath::vec2(1.0 / self.scene.width() as f32, 1.0 / self.scene.height() as f32));
+            for (name, values) in &pass.params {
+                pass.shader.set(name, &values[..]);
+            }
+            gl::BindTexture(gl::TEXTURE_2D, source);
+            gl::DrawArrays(gl::TRIANGLES, 0, 6);
+
+            source = self.ping_pong[i % 2].color_texture(0);
+        }
+
+        gl::BindVertexArray(0);
+        gl::Enable(gl::DEPTH_TEST);
+    }
+}
+
+impl Drop for PostProcessChain {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteVertexArrays(1, &self.quad_vao);
+            gl::DeleteBuffers(1, &self.quad_vbo);
+        }
+    }
+}
+
+/// A quad that fills the entire screen in Normalized Device Coordinates,
+/// positions at location 0 and texture coordinates at location 1
+pub(crate) unsafe fn create_quad() -> (u32, u32) {
+    #[rustfmt::skip]
+    let vertices: [f32; 24] = [
+        // positions // texCoords
+        -1.0,  1.0,  0.0, 1.0,
+        -1.0, -1.0,  0.0, 0.0,
+         1.0, -1.0,  1.0, 0.0,
+
+        -1.0,  1.0,  0.0, 1.0,
+         1.0, -1.0,  1.0, 0.0,
+         1.0,  1.0,  1.0, 1.0,
+    ];
+
+    let (mut vao, mut vbo) = (0, 0);
+    gl::GenVertexArrays(1, &mut vao);
+    gl::GenBuffers(1, &mut vbo);
+    gl::BindVertexArray(vao);
+    gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+    gl::BufferData(
+        gl::ARRAY_BUFFER,
+        mem::size_of_val(&vertices) as GLsizeiptr,
+        vertices.as_ptr() as *const c_void,
+        gl::STATIC_DRAW,
+    );
+    let stride = 4 * mem::size_of::<GLfloat>() as GLsizei;
+    gl::EnableVertexAttribArray(0);
+    gl::VertexAttribPointer(0, 2, gl::FLOAT, gl::FALSE, stride, ptr::null());
+    gl::EnableVertexAttribArray(1);
+    gl::VertexAttribPointer(1, 2, gl::FLOAT, gl::FALSE, stride, (2 * mem::size_of::<GLfloat>()) as *const c_void);
+    gl::BindVertexArray(0);
+    (vao, vbo)
+}
@@ -0,0 +1,295 @@
+#![allow(dead_code)]
+
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use crate::shared::{LogEntry, ShaderError};
+
+/// GLSL source with all `#include`s resolved and `#define`s injected
+pub struct PreprocessedSource {
+    pub source: String,
+    /// every file that went into `source`, the top level file first
+    pub files: Vec<String>,
+    /// for each line of `source`: index into `files` and line number in that file
+    line_map: Vec<(usize, u32)>,
+}
+
+impl PreprocessedSource {
+    /// Maps a line of the preprocessed source back to the file and line it came
+    /// from. Line numbers start at 1, as in driver info logs.
+    pub fn original_location(&self, line: u32) -> Option<(&str, u32)> {
+        let (file, line) = *self.line_map.get((line as usize).checked_sub(1)?)?;
+        Some((&self.files[file], line))
+    }
+
+    /// Points the entries of an info log for `source` at the original files
+    /// and lines, see `original_location`
+    pub fn map_log_locations(&self, log: &mut [LogEntry]) {
+        for entry in log {
+            if let Some((file, line)) = entry.line.and_then(|line| self.original_location(line)) {
+                entry.file = Some(file.into());
+                entry.line = Some(line);
+            }
+        }
+    }
+
+    fn push_line(&mut self, line: &str, file: usize, line_number: u32) {
+        self.source.push_str(line);
+        self.source.push('\n');
+        self.line_map.push((file, line_number));
+    }
+}
+
+/// Reads the shader at `path`, replacing every `#include "file"` line with the
+/// contents of `file` (relative to the directory of the including file) and
+/// adding a `#define NAME value` for each of `defines` right after `#version`.
+/// Every file is included at most once, as if it had `#pragma once`, so
+/// includes don't need include guards.
+pub fn preprocess_file(path: &str, defines: &[(&str, &str)]) -> Result<PreprocessedSource, ShaderError> {
+    preprocess(path, defines, |path| fs::read_to_string(path))
+}
+
+/// Same as `preprocess_file`, but gets the file contents from `read`, so
+/// shaders can be preprocessed from memory as well
+pub fn preprocess<F>(path: &str, defines: &[(&str, &str)], mut read: F) -> Result<PreprocessedSource, ShaderError>
+where
+    F: FnMut(&str) -> io::Result<String>,
+{
+    let mut output = PreprocessedSource { source: String::new(), files: Vec::new(), line_map: Vec::new() };
+    let path = normalize(Path::new(path)).to_string_lossy().into_owned();
+    expand(&path, Some(defines), &mut read, &mut Vec::new(), &mut output)?;
+    Ok(output)
+}
+
+/// Appends the expanded contents of `path` to `output`. `defines` is only
+/// given for the top level file, `stack` holds the files currently being
+/// included to detect include cycles.
+fn expand<F>(
+    path: &str,
+    defines: Option<&[(&str, &str)]>,
+    read: &mut F,
+    stack: &mut Vec<String>,
+    output: &mut PreprocessedSource,
+) -> Result<(), ShaderError>
+where
+    F: FnMut(&str) -> io::Result<String>,
+{
+    let source = read(path).map_err(|error| ShaderError::Io { path: path.into(), error })?;
+    let file = output.files.len();
+    output.files.push(path.into());
+    stack.push(path.into());
+
+    let inject_defines = |output: &mut PreprocessedSource, line_number: u32| {
+        for (name, value) in defines.unwrap_or(&[]) {
+            output.push_line(&format!("#define {} {}", name, value), file, line_number);
+        }
+    };
+    // without a #version directive the defines go first
+    if !source.lines().any(|line| line.trim_start().starts_with("#version")) {
+        inject_defines(output, 1);
+    }
+
+    let directory = Path::new(path).parent().unwrap_or_else(|| Path::new(""));
+    for (i, line) in source.lines().enumerate() {
+        let line_number = i as u32 + 1;
+        let directive = line.trim_start();
+
+        if directive.starts_with("#include") {
+            let error = |message: String| ShaderError::Preprocess { file: path.into(), line: line_number, message };
+            let included = parse_include(directive)
+                .ok_or_else(|| error(format!("malformed include, expected `#include \"file\"`: {}", directive)))?;
+            let included = normalize(&directory.join(included)).to_string_lossy().into_owned();
+            if stack.contains(&included) {
+                return Err(error(format!("include cycle: {} -> {}", stack.join(" -> "), included)));
+            }
+            // already pulled in through another include
+            if output.files.contains(&included) {
+                continue;
+            }
+            expand(&included, None, read, stack, output)?;
+            continue;
+        }
+
+        output.push_line(line, file, line_number);
+        if directive.starts_with("#version") {
+            inject_defines(output, line_number);
+        }
+    }
+
+    stack.pop();
+    Ok(())
+}
+
+/// Returns the file name of an `#include "file"` directive
+fn parse_include(directive: &str) -> Option<&str> {
+    let rest = directive["#include".len()..].trim();
+    if rest.len() < 2 || !rest.starts_with('"') || !rest.ends_with('"') {
+        return None;
+    }
+    Some(&rest[1..rest.len() - 1])
+}
+
+/// Resolves `.` and `..` without touching the file system, so the same file
+/// always gets the same name no matter how it was included
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if normalized.file_name().is_some() => {
+                normalized.pop();
+            }
+            component => normalized.push(component.as_os_str()),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::parse_info_log;
+    use std::collections::HashMap;
+
+    /// preprocesses `path` with `files` as the file system
+    fn run(path: &str, defines: &[(&str, &str)], files: &[(&str, &str)]) -> Result<PreprocessedSource, ShaderError> {
+        let files: HashMap<_, _> = files.iter().cloned().collect();
+        preprocess(path, defines, |path| {
+            // normalize() builds platform paths
+            let path = path.replace('\\', "/");
+            files.get(path.as_str()).map(|source| source.to_string()).ok_or_else(|| io::ErrorKind::NotFound.into())
+        })
+    }
+
+    fn lines(source: &PreprocessedSource) -> Vec<&str> {
+        source.source.lines().collect()
+    }
+
+    fn location(source: &PreprocessedSource, line: u32) -> Option<(String, u32)> {
+        source.original_location(line).map(|(file, line)| (file.replace('\\', "/"), line))
+    }
+
+    const NESTED: &[(&str, &str)] = &[
+        ("shaders/main.fsh", "#version 330 core\n#include \"lib/light.glsl\"\nvoid main() {}"),
+        ("shaders/lib/light.glsl", "#include \"../common.glsl\"\nfloat light() { return common(); }"),
+        ("shaders/common.glsl", "float common() { return 1.0; }"),
+    ];
+
+    #[test]
+    fn resolves_nested_includes_relative_to_the_including_file() {
+        let source = run("shaders/./main.fsh", &[], NESTED).unwrap();
+        assert_eq!(
+            lines(&source),
+            [
+                "#version 330 core",
+                "float common() { return 1.0; }",
+                "float light() { return common(); }",
+                "void main() {}"
+            ]
+        );
+        assert_eq!(source.files.len(), 3);
+    }
+
+    #[test]
+    fn injects_defines_after_version() {
+        let files = [("main.vsh", "// comment\n#version 330 core\nvoid main() {}")];
+        let source = run("main.vsh", &[("N", "4"), ("BLINN", "1")], &files).unwrap();
+        assert_eq!(
+            lines(&source),
+            ["// comment", "#version 330 core", "#define N 4", "#define BLINN 1", "void main() {}"]
+        );
+        // the defines belong to the #version line
+        assert_eq!(location(&source, 3), Some(("main.vsh".into(), 2)));
+        assert_eq!(location(&source, 5), Some(("main.vsh".into(), 3)));
+    }
+
+    #[test]
+    fn injects_defines_first_without_version() {
+        let source = run("lib.glsl", &[("N", "4")], &[("lib.glsl", "float f() { return N; }")]).unwrap();
+        assert_eq!(lines(&source), ["#define N 4", "float f() { return N; }"]);
+    }
+
+    #[test]
+    fn reports_include_cycles() {
+        let files = [("a.glsl", "#include \"b.glsl\""), ("b.glsl", "\n#include \"a.glsl\"")];
+        match run("a.glsl", &[], &files) {
+            Err(ShaderError::Preprocess { file, line, message }) => {
+                assert_eq!((file.as_str(), line), ("b.glsl", 2));
+                assert!(message.contains("include cycle"), "{}", message);
+            }
+            other => panic!("expected an include cycle error, got {:?}", other.err()),
+        }
+        assert!(run("a.glsl", &[], &[("a.glsl", "#include \"a.glsl\"")]).is_err());
+    }
+
+    #[test]
+    fn includes_shared_files_only_once() {
+        let files = [
+            ("main.fsh", "#version 330 core\n#include \"a.glsl\"\n#include \"lib/b.glsl\"\nvoid main() {}"),
+            ("a.glsl", "#include \"common.glsl\"\nfloat a() { return common(); }"),
+            ("lib/b.glsl", "#include \"../common.glsl\"\nfloat b() { return common(); }"),
+            ("common.glsl", "float common() { return 1.0; }"),
+        ];
+        let source = run("main.fsh", &[], &files).unwrap();
+        assert_eq!(
+            lines(&source),
+            [
+                "#version 330 core",
+                "float common() { return 1.0; }",
+                "float a() { return common(); }",
+                "float b() { return common(); }",
+                "void main() {}"
+            ]
+        );
+        assert_eq!(source.files.len(), 4);
+        assert_eq!(location(&source, 4), Some(("lib/b.glsl".into(), 2)));
+    }
+
+    #[test]
+    fn reports_missing_includes() {
+        match run("main.fsh", &[], &[("main.fsh", "#include \"missing.glsl\"")]) {
+            Err(ShaderError::Io { path, .. }) => assert_eq!(path.replace('\\', "/"), "missing.glsl"),
+            other => panic!("expected an io error, got {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn reports_malformed_includes() {
+        for include in &["#include <light.glsl>", "#include \"light.glsl", "#include", "#include \""] {
+            let main = format!("#version 330 core\n{}", include);
+            match run("main.fsh", &[], &[("main.fsh", &main)]) {
+                Err(ShaderError::Preprocess { line, message, .. }) => {
+                    assert_eq!(line, 2);
+                    assert!(message.contains("malformed include"), "{}", message);
+                }
+                other => panic!("expected a malformed include error for {}, got {:?}", include, other.err()),
+            }
+        }
+    }
+
+    #[test]
+    fn maps_expanded_lines_back_to_their_files() {
+        let source = run("shaders/main.fsh", &[("N", "4")], NESTED).unwrap();
+        assert_eq!(location(&source, 1), Some(("shaders/main.fsh".into(), 1)));
+        assert_eq!(location(&source, 2), Some(("shaders/main.fsh".into(), 1)));
+        assert_eq!(location(&source, 3), Some(("shaders/common.glsl".into(), 1)));
+        assert_eq!(location(&source, 4), Some(("shaders/lib/light.glsl".into(), 2)));
+        assert_eq!(location(&source, 5), Some(("shaders/main.fsh".into(), 3)));
+        assert_eq!(location(&source, 0), None);
+        assert_eq!(location(&source, 6), None);
+    }
+
+    #[test]
+    fn maps_info_log_locations_back_to_their_files() {
+        let source = run("shaders/main.fsh", &[], NESTED).unwrap();
+        // what Mesa reports for an error in the expanded line 3
+        let mut log = parse_info_log("0:3(23): error: `common' undeclared\nerror: 1 compilation error", Some("main"));
+        source.map_log_locations(&mut log);
+        assert_eq!(log[0].file.as_ref().map(|file| file.replace('\\', "/")), Some("shaders/lib/light.glsl".into()));
+        assert_eq!(log[0].line, Some(2));
+        assert_eq!(log[0].message, "error: `common' undeclared");
+        // messages without a location keep the top level file
+        assert_eq!((log[1].file.as_deref(), log[1].line), (Some("main"), None));
+    }
+}
@@ -0,0 +1,321 @@
+#![allow(dead_code)]
+
+use std::ffi::CString;
+use std::fmt::Write;
+
+use gl;
+use gl::types::*;
+
+use crate::shared::Shader;
+
+/// An active uniform or vertex attribute of a linked program, as reported by
+/// `glGetActiveUniform`/`glGetActiveAttrib`
+#[derive(Debug, Clone)]
+pub struct ActiveVariable {
+    /// arrays are reported as `name[0]`
+    pub name: String,
+    /// e.g. `gl::FLOAT_VEC3` or `gl::SAMPLER_2D`
+    pub gl_type: GLenum,
+    /// number of array elements, 1 for non-arrays
+    pub size: i32,
+    /// -1 for uniforms inside a uniform block
+    pub location: i32,
+}
+
+impl ActiveVariable {
+    pub fn type_name(&self) -> &'static str {
+        gl_type_name(self.gl_type)
+    }
+
+    pub fn is_sampler(&self) -> bool {
+        is_sampler(self.gl_type)
+    }
+}
+
+/// An active uniform block of a linked program
+#[derive(Debug, Clone)]
+pub struct UniformBlock {
+    pub name: String,
+    pub index: u32,
+    /// binding point the block is currently bound to
+    pub binding: i32,
+    pub data_size: i32,
+    /// names of the uniforms inside the block
+    pub uniforms: Vec<String>,
+}
+
+impl Shader {
+    /// All active uniforms outside of uniform blocks
+    pub unsafe fn active_uniforms(&self) -> Vec<ActiveVariable> {
+        let mut count = 0;
+        gl::GetProgramiv(self.id, gl::ACTIVE_UNIFORMS, &mut count);
+        let mut max_length = 0;
+        gl::GetProgramiv(self.id, gl::ACTIVE_UNIFORM_MAX_LENGTH, &mut max_length);
+
+        let mut uniforms = Vec::new();
+        for index in 0..count as u32 {
+            let mut block_index = -1;
+            gl::GetActiveUniformsiv(self.id, 1, &index, gl::UNIFORM_BLOCK_INDEX, &mut block_index);
+            if block_index != -1 {
+                continue;
+            }
+            let (name, gl_type, size) = active_name(max_length, |len, length, size, type_, name| {
+                gl::GetActiveUniform(self.id, index, len, length, size, type_, name)
+            });
+            // not through the location cache, that one remembers what the tutorial set
+            let c_name = CString::new(name.as_str()).unwrap();
+            let location = gl::GetUniformLocation(self.id, c_name.as_ptr());
+            uniforms.push(ActiveVariable { name, gl_type, size, location });
+        }
+        uniforms
+    }
+
+    /// All active vertex shader inputs, sorted by location
+    pub unsafe fn active_attributes(&self) -> Vec<ActiveVariable> {
+        let mut count = 0;
+        gl::GetProgramiv(self.id, gl::ACTIVE_ATTRIBUTES, &mut count);
+        let mut max_length = 0;
+        gl::GetProgramiv(self.id, gl::ACTIVE_ATTRIBUTE_MAX_LENGTH, &mut max_length);
+
+        let mut attributes: Vec<_> = (0..count as u32)
+            .map(|index| {
+                let (name, gl_type, size) = active_name(max_length, |len, length, size, type_, name| {
+                    gl::GetActiveAttrib(self.id, index, len, length, size, type_, name)
+                });
+                let c_name = CString::new(name.as_str()).unwrap();
+                let location = gl::GetAttribLocation(self.id, c_name.as_ptr());
+                ActiveVariable { name, gl_type, size, location }
+            })
+            .collect();
+        attributes.sort_by_key(|attribute| attribute.location);
+        attributes
+    }
+
+    pub unsafe fn uniform_blocks(&self) -> Vec<UniformBlock> {
+        let mut count = 0;
+        gl::GetProgramiv(self.id, gl::ACTIVE_UNIFORM_BLOCKS, &mut count);
+        let mut max_length = 0;
+        gl::GetProgramiv(self.id, gl::ACTIVE_UNIFORM_BLOCK_MAX_NAME_LENGTH, &mut max_length);
+        let mut max_uniform_length = 0;
+        gl::GetProgramiv(self.id, gl::ACTIVE_UNIFORM_MAX_LENGTH, &mut max_uniform_length);
+
+        (0..count as u32)
+            .map(|index| {
+                let mut name = vec![0u8; max_length.max(1) as usize];
+                let mut length = 0;
+                gl::GetActiveUniformBlockName(
+                    self.id,
+                    index,
+                    max_length,
+                    &mut length,
+                    name.as_mut_ptr() as *mut GLchar,
+                );
+                name.truncate(length as usize);
+
+                let block_param = |pname| {
+                    let mut value = 0;
+                    gl::GetActiveUniformBlockiv(self.id, index, pname, &mut value);
+                    value
+                };
+                let binding = block_param(gl::UNIFORM_BLOCK_BINDING);
+                let data_size = block_param(gl::UNIFORM_BLOCK_DATA_SIZE);
+                let mut indices = vec![0; block_param(gl::UNIFORM_BLOCK_ACTIVE_UNIFORMS) as usize];
+                if !indices.is_empty() {
+                    gl::GetActiveUniformBlockiv(
+                        self.id,
+                        index,
+                        gl::UNIFORM_BLOCK_ACTIVE_UNIFORM_INDICES,
+                        indices.as_mut_ptr(),
+                    );
+                }
+                let uniforms = indices
+                    .iter()
+                    .map(|uniform| {
+                        active_name(max_uniform_length, |len, length, size, type_, name| {
+                            gl::GetActiveUniform(self.id, *uniform as u32, len, length, size, type_, name)
+                        })
+                        .0
+                    })
+                    .collect();
+
+                UniformBlock { name: String::from_utf8_lossy(&name).into_owned(), index, binding, data_size, uniforms }
+            })
+            .collect()
+    }
+
+    /// Compares the vertex shader inputs against a vertex layout given as
+    /// `(location, GL type)` pairs, e.g. `Vertex::LAYOUT`. Returns a message for
+    /// every input that isn't fed by the layout or has a different type; layout
+    /// entries the shader doesn't use are fine.
+    pub unsafe fn check_vertex_layout(&self, layout: &[(u32, GLenum)]) -> Vec<String> {
+        let mut problems = Vec::new();
+        for attribute in self.active_attributes() {
+            // built-ins like gl_VertexID are listed as well, but have no location
+            if attribute.location < 0 {
+                continue;
+            }
+            match layout.iter().find(|(location, _)| *location == attribute.location as u32) {
+                None => problems.push(format!(
+                    "vertex input '{}' (location {}) is not provided by the vertex layout",
+                    attribute.name, attribute.location
+                )),
+                Some((_, gl_type)) if *gl_type != attribute.gl_type => problems.push(format!(
+                    "vertex input '{}' (location {}) is a {}, but the vertex layout provides a {}",
+                    attribute.name,
+                    attribute.location,
+                    attribute.type_name(),
+                    gl_type_name(*gl_type)
+                )),
+                _ => {}
+            }
+        }
+        problems
+    }
+
+    /// Sampler uniforms that were never set through this `Shader`. They all
+    /// sample texture unit 0, which is rarely intended if there's more than one.
+    pub unsafe fn unset_samplers(&self) -> Vec<ActiveVariable> {
+        self.active_uniforms()
+            .into_iter()
+            .filter(|uniform| uniform.is_sampler() && !self.was_set(&uniform.name))
+            .collect()
+    }
+
+    /// Current value of `uniform` as text, e.g. `vec3(0.2, 1, 0.5)`. Only the
+    /// first element of arrays is read.
+    pub unsafe fn uniform_value(&self, uniform: &ActiveVariable) -> String {
+        let components = gl_type_components(uniform.gl_type);
+        if uniform.location < 0 || components == 0 {
+            return "?".into();
+        }
+        let values: Vec<String> = if is_float_type(uniform.gl_type) {
+            let mut values = [0.0f32; 16];
+            gl::GetUniformfv(self.id, uniform.location, values.as_mut_ptr());
+            values[..components].iter().map(|v| v.to_string()).collect()
+        } else {
+            let mut values = [0i32; 4];
+            gl::GetUniformiv(self.id, uniform.location, values.as_mut_ptr());
+            values[..components].iter().map(|v| v.to_string()).collect()
+        };
+        if components == 1 {
+            values[0].clone()
+        } else {
+            format!("{}({})", uniform.type_name(), values.join(", "))
+        }
+    }
+
+    /// Human readable listing of the program's inputs and uniforms with their
+    /// current values, for debug output
+    pub unsafe fn describe(&self) -> String {
+        let mut description = String::new();
+        let _ = writeln!(description, "program {}", self.id);
+        for attribute in self.active_attributes() {
+            let _ = writeln!(
+                description,
+                "  in {} {} (location {})",
+                attribute.type_name(),
+                attribute.name,
+                attribute.location
+            );
+        }
+        for uniform in self.active_uniforms() {
+            let array = if uniform.size > 1 { format!(" [{}]", uniform.size) } else { String::new() };
+            let _ = writeln!(
+                description,
+                "  uniform {} {}{} = {}",
+                uniform.type_name(),
+                uniform.name,
+                array,
+                self.uniform_value(&uniform)
+            );
+        }
+        for block in self.uniform_blocks() {
+            let _ = writeln!(
+                description,
+                "  uniform block {} (binding {}, {} bytes): {}",
+                block.name,
+                block.binding,
+                block.data_size,
+                block.uniforms.join(", ")
+            );
+        }
+        description
+    }
+}
+
+/// Calls one of the `glGetActive*` functions with a name buffer of `max_length`
+/// and returns name, type and size
+unsafe fn active_name<F>(max_length: i32, get: F) -> (String, GLenum, i32)
+where
+    F: FnOnce(GLsizei, *mut GLsizei, *mut GLint, *mut GLenum, *mut GLchar),
+{
+    let mut name = vec![0u8; max_length.max(1) as usize];
+    let mut length = 0;
+    let mut size = 0;
+    let mut gl_type = 0;
+    get(max_length, &mut length, &mut size, &mut gl_type, name.as_mut_ptr() as *mut GLchar);
+    name.truncate(length as usize);
+    (String::from_utf8_lossy(&name).into_owned(), gl_type, size)
+}
+
+/// GLSL name of a uniform or attribute type
+pub fn gl_type_name(gl_type: GLenum) -> &'static str {
+    match gl_type {
+        gl::FLOAT => "float",
+        gl::FLOAT_VEC2 => "vec2",
+        gl::FLOAT_VEC3 => "vec3",
+        gl::FLOAT_VEC4 => "vec4",
+        gl::INT => "int",
+        gl::INT_VEC2 => "ivec2",
+        gl::INT_VEC3 => "ivec3",
+        gl::INT_VEC4 => "ivec4",
+        gl::UNSIGNED_INT => "uint",
+        gl::BOOL => "bool",
+        gl::FLOAT_MAT2 => "mat2",
+        gl::FLOAT_MAT3 => "mat3",
+        gl::FLOAT_MAT4 => "mat4",
+        gl::SAMPLER_2D => "sampler2D",
+        gl::SAMPLER_3D => "sampler3D",
+        gl::SAMPLER_CUBE => "samplerCube",
+        gl::SAMPLER_2D_SHADOW => "sampler2DShadow",
+        gl::SAMPLER_2D_MULTISAMPLE => "sampler2DMS",
+        gl::SAMPLER_2D_ARRAY => "sampler2DArray",
+        _ => "unknown",
+    }
+}
+
+pub fn is_sampler(gl_type: GLenum) -> bool {
+    matches!(
+        gl_type,
+        gl::SAMPLER_1D
+            | gl::SAMPLER_2D
+            | gl::SAMPLER_3D
+            | gl::SAMPLER_CUBE
+            | gl::SAMPLER_2D_SHADOW
+            | gl::SAMPLER_CUBE_SHADOW
+            | gl::SAMPLER_2D_MULTISAMPLE
+            | gl::SAMPLER_2D_ARRAY
+            | gl::SAMPLER_2D_ARRAY_SHADOW
+    )
+}
+
+fn is_float_type(gl_type: GLenum) -> bool {
+    matches!(
+        gl_type,
+        gl::FLOAT | gl::FLOAT_VEC2 | gl::FLOAT_VEC3 | gl::FLOAT_VEC4 | gl::FLOAT_MAT2 | gl::FLOAT_MAT3 | gl::FLOAT_MAT4
+    )
+}
+
+/// number of scalars in a value of `gl_type`, 0 for types we can't read back
+fn gl_type_components(gl_type: GLenum) -> usize {
+    match gl_type {
+        gl::FLOAT | gl::INT | gl::UNSIGNED_INT | gl::BOOL => 1,
+        gl::FLOAT_VEC2 | gl::INT_VEC2 => 2,
+        gl::FLOAT_VEC3 | gl::INT_VEC3 => 3,
+        gl::FLOAT_VEC4 | gl::INT_VEC4 | gl::FLOAT_MAT2 => 4,
+        gl::FLOAT_MAT3 => 9,
+        gl::FLOAT_MAT4 => 16,
+        gl_type if is_sampler(gl_type) => 1,
+        _ => 0,
+    }
+}
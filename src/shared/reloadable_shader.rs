@@ -0,0 +1,220 @@
+#![allow(dead_code)]
+
+use std::fs;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::shared::{Shader, ShaderError, ShaderStage};
+
+/// How often the source files are checked for modifications
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Bumped for every forced reload, see `request_shader_reload`
+static RELOAD_REQUESTS: AtomicUsize = AtomicUsize::new(0);
+static RELOAD_KEY_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// Makes every `ReloadableShader` recompile on its next `reload_if_changed`,
+/// whether its files changed or not
+pub fn request_shader_reload() {
+    RELOAD_REQUESTS.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Calls `request_shader_reload` once per key press, given the current state
+/// of the reload key (as polled every frame in `process_input`)
+pub fn handle_reload_key(pressed: bool) {
+    if pressed && !RELOAD_KEY_DOWN.swap(true, Ordering::SeqCst) {
+        request_shader_reload();
+    } else if !pressed {
+        RELOAD_KEY_DOWN.store(false, Ordering::SeqCst);
+    }
+}
+
+/// A `Shader` which recompiles itself when one of its source files (including
+/// `#include`d ones) changes on disk. If the new sources fail to compile the
+/// error is printed and the last working program is kept, so a typo doesn't
+/// take down the running tutorial.
+///
+/// Dereferences to the current `Shader`, so it can be used in place of one.
+pub struct ReloadableShader {
+    shader: Shader,
+    stages: Vec<(ShaderStage, String)>,
+    defines: Vec<(String, String)>,
+    /// all files the program was built from and their modification times
+    watched: Vec<(String, Option<SystemTime>)>,
+    last_poll: Instant,
+    reload_requests: usize,
+}
+
+impl ReloadableShader {
+    pub fn new(vertex_path: &str, fragment_path: &str) -> ReloadableShader {
+        Shader::builder().vertex(vertex_path).fragment(fragment_path).build_reloadable()
+    }
+
+    /// See `Shader::with_defines`
+    pub fn with_defines(vertex_path: &str, fragment_path: &str, defines: &[(&str, &str)]) -> ReloadableShader {
+        Shader::builder().vertex(vertex_path).fragment(fragment_path).defines(defines).build_reloadable()
+    }
+
+    pub fn with_geometry_shader(vertex_path: &str, fragment_path: &str, geometry_path: &str) -> ReloadableShader {
+        Shader::builder().vertex(vertex_path).geometry(geometry_path).fragment(fragment_path).build_reloadable()
+    }
+
+    /// Use `ShaderBuilder::build_reloadable` for other combinations of stages
+    pub(crate) fn from_stages(stages: Vec<(ShaderStage, String)>, defines: Vec<(String, String)>) -> ReloadableShader {
+        let mut shader = ReloadableShader {
+            shader: Shader::from_program(0),
+            stages,
+            defines,
+            watched: Vec::new(),
+            last_poll: Instant::now(),
+            reload_requests: RELOAD_REQUESTS.load(Ordering::SeqCst),
+        };
+        shader.reload().unwrap_or_else(|e| panic!("{}", e));
+        shader
+    }
+
+    /// Recompiles the program if a source file was modified or a reload was
+    /// requested. Returns true if the program was replaced, in which case
+    /// uniforms that are only set once (e.g. sampler units) have to be set again.
+    pub fn reload_if_changed(&mut self) -> bool {
+        let requests = RELOAD_REQUESTS.load(Ordering::SeqCst);
+        let forced = requests != self.reload_requests;
+        if !forced && self.last_poll.elapsed() < POLL_INTERVAL {
+            return false;
+        }
+        self.reload_requests = requests;
+        self.last_poll = Instant::now();
+
+        let changed = self.watched.iter().any(|(path, modified)| modification_time(path) != *modified);
+        if !forced && !changed {
+            return false;
+        }
+
+        match self.reload() {
+            Ok(()) => true,
+            Err(e) => {
+                println!("{}\nkeeping the previous shader program", e);
+                false
+            }
+        }
+    }
+
+    /// Recompiles the program now. Keeps the current program on error.
+    pub fn reload(&mut self) -> Result<(), ShaderError> {
+        // look at the files before reading them, so changes made while compiling aren't missed
+        let stage_paths: Vec<_> = self.stages.iter().map(|(_, path)| path.as_str()).collect();
+        let previous = rewatch(&stage_paths, &self.watched);
+        let stages: Vec<_> = self.stages.iter().map(|(stage, path)| (*stage, path.as_str())).collect();
+        let defines: Vec<_> = self.defines.iter().map(|(name, value)| (name.as_str(), value.as_str())).collect();
+        let result = unsafe { Shader::compile(&stages, &defines) };
+
+        let (shader, files) = match result {
+            Ok(built) => built,
+            Err(e) => {
+                self.watched = previous;
+                return Err(e);
+            }
+        };
+        self.watched = watch_built_files(&stage_paths, previous, files);
+
+        let old = std::mem::replace(&mut self.shader, shader);
+        if old.id != 0 {
+            unsafe { gl::DeleteProgram(old.id) };
+        }
+        Ok(())
+    }
+}
+
+impl Deref for ReloadableShader {
+    type Target = Shader;
+
+    fn deref(&self) -> &Shader {
+        &self.shader
+    }
+}
+
+/// The stage files and everything `watched` by the last build, with their
+/// current modification times. This is what stays watched when a build fails:
+/// it may have stopped before reaching the includes, and fixing one of them
+/// has to trigger a reload too.
+fn rewatch(stage_paths: &[&str], watched: &[(String, Option<SystemTime>)]) -> Vec<(String, Option<SystemTime>)> {
+    let mut paths: Vec<String> = stage_paths.iter().map(|path| path.to_string()).collect();
+    for (path, _) in watched {
+        if !paths.contains(path) {
+            paths.push(path.clone());
+        }
+    }
+    paths.into_iter().map(|path| (path.clone(), modification_time(&path))).collect()
+}
+
+/// After a successful build the stage files and the `files` it read are
+/// watched, so includes that were removed drop out. Times are taken from
+/// `rewatch` where possible, as those were looked at before compiling.
+fn watch_built_files(
+    stage_paths: &[&str],
+    rewatched: Vec<(String, Option<SystemTime>)>,
+    files: Vec<String>,
+) -> Vec<(String, Option<SystemTime>)> {
+    let mut watched: Vec<_> =
+        rewatched.iter().filter(|(path, _)| stage_paths.contains(&path.as_str())).cloned().collect();
+    for file in files {
+        if !watched.iter().any(|(path, _)| *path == file) {
+            let modified = match rewatched.iter().find(|(path, _)| *path == file) {
+                Some((_, modified)) => *modified,
+                None => modification_time(&file),
+            };
+            watched.push((file, modified));
+        }
+    }
+    watched
+}
+
+fn modification_time(path: &str) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(watched: &[(String, Option<SystemTime>)]) -> Vec<&str> {
+        watched.iter().map(|(path, _)| path.as_str()).collect()
+    }
+
+    #[test]
+    fn includes_stay_watched_across_a_failed_reload() {
+        let dir = std::env::temp_dir().join("gfx-rs-reloadable-shader-test");
+        fs::create_dir_all(dir.join("common")).unwrap();
+        let vertex = dir.join("lit.vsh").to_string_lossy().into_owned();
+        let fragment = dir.join("lit.fsh").to_string_lossy().into_owned();
+        let include = dir.join("common/lighting.glsl").to_string_lossy().into_owned();
+        fs::write(&vertex, "#version 330 core\nvoid main() {}\n").unwrap();
+        fs::write(&fragment, "#version 330 core\n#include \"common/lighting.glsl\"\nvoid main() {}\n").unwrap();
+        fs::write(&include, "vec3 lighting() { return vec3(1.0); }\n").unwrap();
+        let stages = [vertex.as_str(), fragment.as_str()];
+
+        let built =
+            watch_built_files(&stages, rewatch(&stages, &[]), vec![vertex.clone(), fragment.clone(), include.clone()]);
+        assert_eq!(paths(&built), [&vertex, &fragment, &include]);
+
+        // the include breaks: the failed build keeps watching it, at its current time so it isn't reloaded again
+        fs::write(&include, "vec3 lighting() { return vec3(1.0) }\n").unwrap();
+        let failed = rewatch(&stages, &built);
+        assert_eq!(paths(&failed), [&vertex, &fragment, &include]);
+        assert!(failed.iter().all(|(path, modified)| modification_time(path) == *modified));
+
+        // the include gets fixed: still watched by the rebuilt program
+        fs::write(&include, "vec3 lighting() { return vec3(1.0); }\n").unwrap();
+        let fixed = watch_built_files(
+            &stages,
+            rewatch(&stages, &failed),
+            vec![vertex.clone(), fragment.clone(), include.clone()],
+        );
+        assert_eq!(paths(&fixed), [&vertex, &fragment, &include]);
+
+        // and drops out once it is no longer included
+        let without = watch_built_files(&stages, rewatch(&stages, &fixed), vec![vertex.clone(), fragment.clone()]);
+        assert_eq!(paths(&without), [&vertex, &fragment]);
+    }
+}
@@ -1,8 +1,9 @@
 #![allow(dead_code)]
 
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::ffi::{CStr, CString};
-use std::fs::File;
-use std::io::Read;
+use std::io;
 use std::ptr;
 use std::str;
 
@@ -11,52 +12,53 @@ use cgmath::{Matrix4, Vector3};
 use gl;
 use gl::types::*;
 
+use crate::shared::{
+    parse_info_log, preprocess_file, PreprocessedSource, ReloadableShader, ShaderError, ShaderStage, Uniform,
+};
+
 pub struct Shader {
     pub id: u32,
+    /// uniform locations looked up so far, -1 for names that aren't active uniforms
+    locations: RefCell<HashMap<String, i32>>,
+    /// missing uniforms that have been warned about
+    reported_missing: RefCell<HashSet<String>>,
 }
 
 /// NOTE: mixture of `shader_s.h` and `shader_m.h` (the latter just contains
 /// a few more setters for uniforms)
 impl Shader {
     pub fn new(vertex_path: &str, fragment_path: &str) -> Shader {
-        let mut shader = Shader { id: 0 };
-        // 1. retrieve the vertex/fragment source code from filesystem
-        let mut v_shader_file = File::open(vertex_path).unwrap_or_else(|_| panic!("Failed to open {}", vertex_path));
-        let mut f_shader_file =
-            File::open(fragment_path).unwrap_or_else(|_| panic!("Failed to open {}", fragment_path));
-        let mut vertex_code = String::new();
-        let mut fragment_code = String::new();
-        v_shader_file.read_to_string(&mut vertex_code).expect("Failed to read vertex shader");
-        f_shader_file.read_to_string(&mut fragment_code).expect("Failed to read fragment shader");
-
-        let v_shader_code = CString::new(vertex_code.as_bytes()).unwrap();
-        let f_shader_code = CString::new(fragment_code.as_bytes()).unwrap();
-
-        // 2. compile shaders
-        unsafe {
-            // vertex shader
-            let vertex = gl::CreateShader(gl::VERTEX_SHADER);
-            gl::ShaderSource(vertex, 1, &v_shader_code.as_ptr(), ptr::null());
-            gl::CompileShader(vertex);
-            shader.check_compile_errors(vertex, "VERTEX");
-            // fragment shader
-            let fragment = gl::CreateShader(gl::FRAGMENT_SHADER);
-            gl::ShaderSource(fragment, 1, &f_shader_code.as_ptr(), ptr::null());
-            gl::CompileShader(fragment);
-            shader.check_compile_errors(fragment, "FRAGMENT");
-            // shader program
-            let id = gl::CreateProgram();
-            gl::AttachShader(id, vertex);
-            gl::AttachShader(id, fragment);
-            gl::LinkProgram(id);
-            shader.check_compile_errors(id, "PROGRAM");
-            // delete the shaders as they're linked into our program now and no longer necessary
-            gl::DeleteShader(vertex);
-            gl::DeleteShader(fragment);
-            shader.id = id
-        }
+        Shader::builder().vertex(vertex_path).fragment(fragment_path).build()
+    }
 
-        shader
+    /// Like `new`, but returns missing files and compile/link errors instead of panicking
+    pub fn try_new(vertex_path: &str, fragment_path: &str) -> Result<Shader, ShaderError> {
+        Shader::builder().vertex(vertex_path).fragment(fragment_path).try_build()
+    }
+
+    /// Shader whose sources get `#define NAME value` for each of `defines`,
+    /// injected right after their `#version` line
+    pub fn with_defines(vertex_path: &str, fragment_path: &str, defines: &[(&str, &str)]) -> Shader {
+        Shader::builder().vertex(vertex_path).fragment(fragment_path).defines(defines).build()
+    }
+
+    pub fn try_with_defines(
+        vertex_path: &str,
+        fragment_path: &str,
+        defines: &[(&str, &str)],
+    ) -> Result<Shader, ShaderError> {
+        Shader::builder().vertex(vertex_path).fragment(fragment_path).defines(defines).try_build()
+    }
+
+    /// For any other combination of stages and defines, e.g.
+    /// `Shader::builder().vertex(vs).geometry(gs).fragment(fs).define("N", "4").build()`
+    pub fn builder() -> ShaderBuilder {
+        ShaderBuilder::default()
+    }
+
+    /// Wraps an already linked program
+    pub fn from_program(id: u32) -> Shader {
+        Shader { id, locations: RefCell::new(HashMap::new()), reported_missing: RefCell::new(HashSet::new()) }
     }
 
     /// activate the shader
@@ -65,94 +67,264 @@ impl Shader {
         gl::UseProgram(self.id)
     }
 
+    /// Sets the uniform `name` of this program, which has to be in use.
+    /// Works for anything implementing `Uniform`, e.g.
+    /// `shader.set("lightPositions", &[pos0, pos1])`.
+    pub unsafe fn set<T: Uniform + ?Sized>(&self, name: &str, value: &T) {
+        value.set_uniform(self.uniform_location(name));
+    }
+
+    /// Like `set`, but silently skips uniforms the program doesn't have. For
+    /// optional uniforms, e.g. the texture samplers in `Mesh::draw`. Returns
+    /// whether the uniform exists.
+    pub unsafe fn try_set<T: Uniform + ?Sized>(&self, name: &str, value: &T) -> bool {
+        let location = self.lookup_location(name, false);
+        value.set_uniform(location);
+        location != -1
+    }
+
+    /// Location of the uniform `name`, cached after the first lookup. Unknown
+    /// (or optimized out) uniforms get -1, which GL ignores, and a warning the
+    /// first time they're used.
+    pub unsafe fn uniform_location(&self, name: &str) -> i32 {
+        self.lookup_location(name, true)
+    }
+
+    /// whether the active uniform `name` has been set through this `Shader`
+    pub(crate) fn was_set(&self, name: &str) -> bool {
+        matches!(self.locations.borrow().get(name), Some(location) if *location != -1)
+    }
+
+    unsafe fn lookup_location(&self, name: &str, warn: bool) -> i32 {
+        let cached = self.locations.borrow().get(name).cloned();
+        let location = cached.unwrap_or_else(|| {
+            let location = match CString::new(name) {
+                Ok(c_name) => gl::GetUniformLocation(self.id, c_name.as_ptr()),
+                Err(_) => -1,
+            };
+            self.locations.borrow_mut().insert(name.to_string(), location);
+            location
+        });
+        // misses cached by `try_set` still get their warning on the first `set`
+        if location == -1 && warn && self.reported_missing.borrow_mut().insert(name.to_string()) {
+            println!("WARNING::SHADER: program {} has no active uniform '{}'", self.id, name);
+        }
+        location
+    }
+
     /// utility uniform functions
     /// ------------------------------------------------------------------------
     pub unsafe fn set_bool(&self, name: &CStr, value: bool) {
-        gl::Uniform1i(gl::GetUniformLocation(self.id, name.as_ptr()), value as i32);
+        gl::Uniform1i(self.c_location(name), value as i32);
     }
     /// ------------------------------------------------------------------------
     pub unsafe fn set_int(&self, name: &CStr, value: i32) {
-        gl::Uniform1i(gl::GetUniformLocation(self.id, name.as_ptr()), value);
+        gl::Uniform1i(self.c_location(name), value);
     }
     /// ------------------------------------------------------------------------
     pub unsafe fn set_float(&self, name: &CStr, value: f32) {
-        gl::Uniform1f(gl::GetUniformLocation(self.id, name.as_ptr()), value);
+        gl::Uniform1f(self.c_location(name), value);
     }
     /// ------------------------------------------------------------------------
     pub unsafe fn set_vector3(&self, name: &CStr, value: &Vector3<f32>) {
-        gl::Uniform3fv(gl::GetUniformLocation(self.id, name.as_ptr()), 1, value.as_ptr());
+        gl::Uniform3fv(self.c_location(name), 1, value.as_ptr());
     }
     /// ------------------------------------------------------------------------
     pub unsafe fn set_vec3(&self, name: &CStr, x: f32, y: f32, z: f32) {
-        gl::Uniform3f(gl::GetUniformLocation(self.id, name.as_ptr()), x, y, z);
+        gl::Uniform3f(self.c_location(name), x, y, z);
     }
     /// ------------------------------------------------------------------------
     pub unsafe fn set_mat4(&self, name: &CStr, mat: &Matrix4<f32>) {
-        gl::UniformMatrix4fv(gl::GetUniformLocation(self.id, name.as_ptr()), 1, gl::FALSE, mat.as_ptr());
+        gl::UniformMatrix4fv(self.c_location(name), 1, gl::FALSE, mat.as_ptr());
     }
 
-    /// utility function for checking shader compilation/linking errors.
-    /// ------------------------------------------------------------------------
-    unsafe fn check_compile_errors(&self, shader: u32, type_: &str) {
-        check_compile_errors(shader, type_)
+    unsafe fn c_location(&self, name: &CStr) -> i32 {
+        match name.to_str() {
+            Ok(name) => self.uniform_location(name),
+            Err(_) => gl::GetUniformLocation(self.id, name.as_ptr()),
+        }
     }
 
     /// Only used in 4.9 Geometry shaders - ignore until then (shader.h in original C++)
     pub fn with_geometry_shader(vertex_path: &str, fragment_path: &str, geometry_path: &str) -> Self {
-        let mut shader = Shader { id: 0 };
-        // 1. retrieve the vertex/fragment source code from filesystem
-        let mut v_shader_file = File::open(vertex_path).unwrap_or_else(|_| panic!("Failed to open {}", vertex_path));
-        let mut f_shader_file =
-            File::open(fragment_path).unwrap_or_else(|_| panic!("Failed to open {}", fragment_path));
-        let mut g_shader_file =
-            File::open(geometry_path).unwrap_or_else(|_| panic!("Failed to open {}", geometry_path));
-        let mut vertex_code = String::new();
-        let mut fragment_code = String::new();
-        let mut geometry_code = String::new();
-        v_shader_file.read_to_string(&mut vertex_code).expect("Failed to read vertex shader");
-        f_shader_file.read_to_string(&mut fragment_code).expect("Failed to read fragment shader");
-        g_shader_file.read_to_string(&mut geometry_code).expect("Failed to read geometry shader");
-
-        let v_shader_code = CString::new(vertex_code.as_bytes()).unwrap();
-        let f_shader_code = CString::new(fragment_code.as_bytes()).unwrap();
-        let g_shader_code = CString::new(geometry_code.as_bytes()).unwrap();
-
-        // 2. compile shaders
-        unsafe {
-            // vertex shader
-            let vertex = gl::CreateShader(gl::VERTEX_SHADER);
-            gl::ShaderSource(vertex, 1, &v_shader_code.as_ptr(), ptr::null());
-            gl::CompileShader(vertex);
-            shader.check_compile_errors(vertex, "VERTEX");
-            // fragment Shader
-            let fragment = gl::CreateShader(gl::FRAGMENT_SHADER);
-            gl::ShaderSource(fragment, 1, &f_shader_code.as_ptr(), ptr::null());
-            gl::CompileShader(fragment);
-            shader.check_compile_errors(fragment, "FRAGMENT");
-            // geometry shader
-            let geometry = gl::CreateShader(gl::GEOMETRY_SHADER);
-            gl::ShaderSource(geometry, 1, &g_shader_code.as_ptr(), ptr::null());
-            gl::CompileShader(geometry);
-            shader.check_compile_errors(geometry, "GEOMETRY");
-
-            // shader Program
-            let id = gl::CreateProgram();
-            gl::AttachShader(id, vertex);
-            gl::AttachShader(id, fragment);
-            gl::AttachShader(id, geometry);
-            gl::LinkProgram(id);
-            shader.check_compile_errors(id, "PROGRAM");
-            // delete the shaders as they're linked into our program now and no longer necessary
-            gl::DeleteShader(vertex);
-            gl::DeleteShader(fragment);
-            gl::DeleteShader(geometry);
-            shader.id = id;
+        Shader::builder().vertex(vertex_path).geometry(geometry_path).fragment(fragment_path).build()
+    }
+
+    pub fn try_with_geometry_shader(
+        vertex_path: &str,
+        fragment_path: &str,
+        geometry_path: &str,
+    ) -> Result<Shader, ShaderError> {
+        Shader::builder().vertex(vertex_path).geometry(geometry_path).fragment(fragment_path).try_build()
+    }
+
+    /// Preprocesses and compiles every stage from its source file and links them
+    /// into a program. Also returns all files the sources were read from,
+    /// including `#include`d ones. Nothing is leaked on failure.
+    pub(crate) unsafe fn compile(
+        stages: &[(ShaderStage, &str)],
+        defines: &[(&str, &str)],
+    ) -> Result<(Shader, Vec<String>), ShaderError> {
+        check_stages(stages)?;
+        let mut shaders = Vec::with_capacity(stages.len());
+        let mut files = Vec::new();
+        for &(stage, path) in stages {
+            let shader = preprocess_file(path, defines).and_then(|source| {
+                files.extend(source.files.iter().cloned());
+                compile_shader(stage, &source)
+            });
+            match shader {
+                Ok(shader) => shaders.push(shader),
+                Err(e) => {
+                    shaders.iter().for_each(|shader| gl::DeleteShader(*shader));
+                    return Err(e);
+                }
+            }
         }
 
-        shader
+        let id = gl::CreateProgram();
+        shaders.iter().for_each(|shader| gl::AttachShader(id, *shader));
+        gl::LinkProgram(id);
+        // delete the shaders as they're linked into our program now and no longer necessary
+        shaders.iter().for_each(|shader| gl::DeleteShader(*shader));
+
+        let mut success = gl::FALSE as GLint;
+        gl::GetProgramiv(id, gl::LINK_STATUS, &mut success);
+        if success != gl::TRUE as GLint {
+            let log = parse_info_log(&program_info_log(id), None);
+            gl::DeleteProgram(id);
+            return Err(ShaderError::Link { log });
+        }
+
+        Ok((Shader::from_program(id), files))
     }
 }
 
+/// Source files and defines of a program with an arbitrary combination of
+/// stages, see `Shader::builder`
+#[derive(Debug, Clone, Default)]
+pub struct ShaderBuilder {
+    stages: Vec<(ShaderStage, String)>,
+    defines: Vec<(String, String)>,
+}
+
+impl ShaderBuilder {
+    pub fn stage(mut self, stage: ShaderStage, path: &str) -> Self {
+        self.stages.push((stage, path.into()));
+        self
+    }
+
+    pub fn vertex(self, path: &str) -> Self {
+        self.stage(ShaderStage::Vertex, path)
+    }
+
+    pub fn fragment(self, path: &str) -> Self {
+        self.stage(ShaderStage::Fragment, path)
+    }
+
+    pub fn geometry(self, path: &str) -> Self {
+        self.stage(ShaderStage::Geometry, path)
+    }
+
+    /// needs an OpenGL 4.0 context, like `tess_evaluation`
+    pub fn tess_control(self, path: &str) -> Self {
+        self.stage(ShaderStage::TessControl, path)
+    }
+
+    pub fn tess_evaluation(self, path: &str) -> Self {
+        self.stage(ShaderStage::TessEvaluation, path)
+    }
+
+    /// needs an OpenGL 4.3 context; can't be combined with other stages
+    pub fn compute(self, path: &str) -> Self {
+        self.stage(ShaderStage::Compute, path)
+    }
+
+    /// Adds `#define name value` to every stage, see `Shader::with_defines`
+    pub fn define(mut self, name: &str, value: &str) -> Self {
+        self.defines.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn defines(self, defines: &[(&str, &str)]) -> Self {
+        defines.iter().fold(self, |builder, (name, value)| builder.define(name, value))
+    }
+
+    pub fn build(&self) -> Shader {
+        self.try_build().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_build(&self) -> Result<Shader, ShaderError> {
+        let stages: Vec<_> = self.stages.iter().map(|(stage, path)| (*stage, path.as_str())).collect();
+        let defines: Vec<_> = self.defines.iter().map(|(name, value)| (name.as_str(), value.as_str())).collect();
+        unsafe { Shader::compile(&stages, &defines).map(|(shader, _)| shader) }
+    }
+
+    /// Builds a `ReloadableShader` from the same stages and defines
+    pub fn build_reloadable(&self) -> ReloadableShader {
+        ReloadableShader::from_stages(self.stages.clone(), self.defines.clone())
+    }
+}
+
+/// Rejects combinations of stages that can never link, before compiling anything
+fn check_stages(stages: &[(ShaderStage, &str)]) -> Result<(), ShaderError> {
+    let has = |stage| stages.iter().any(|(s, _)| *s == stage);
+    let count = |stage| stages.iter().filter(|(s, _)| *s == stage).count();
+    let message = if stages.is_empty() {
+        "no shader stages given".to_string()
+    } else if let Some((stage, _)) = stages.iter().find(|(stage, _)| count(*stage) > 1) {
+        format!("{} stage given more than once", stage)
+    } else if has(ShaderStage::Compute) && stages.len() > 1 {
+        "a compute shader can't be combined with other stages".to_string()
+    } else if !has(ShaderStage::Compute) && !has(ShaderStage::Vertex) {
+        "a vertex shader is required".to_string()
+    } else if has(ShaderStage::TessControl) && !has(ShaderStage::TessEvaluation) {
+        "a tessellation control shader requires a tessellation evaluation shader".to_string()
+    } else {
+        return Ok(());
+    };
+    Err(ShaderError::Stages { message })
+}
+
+/// Compiles a single shader stage. Locations in the info log are mapped back
+/// to the original files and lines.
+unsafe fn compile_shader(stage: ShaderStage, source: &PreprocessedSource) -> Result<u32, ShaderError> {
+    let code = CString::new(source.source.as_bytes()).map_err(|e| ShaderError::Io {
+        path: source.files[0].clone(),
+        error: io::Error::new(io::ErrorKind::InvalidData, e),
+    })?;
+
+    let shader = gl::CreateShader(stage.gl_type());
+    gl::ShaderSource(shader, 1, &code.as_ptr(), ptr::null());
+    gl::CompileShader(shader);
+
+    let mut success = gl::FALSE as GLint;
+    gl::GetShaderiv(shader, gl::COMPILE_STATUS, &mut success);
+    if success != gl::TRUE as GLint {
+        let mut log = parse_info_log(&shader_info_log(shader), Some(&source.files[0]));
+        source.map_log_locations(&mut log);
+        gl::DeleteShader(shader);
+        return Err(ShaderError::Compile { stage, log });
+    }
+    Ok(shader)
+}
+
+pub unsafe fn shader_info_log(shader: u32) -> String {
+    let mut len = 0;
+    gl::GetShaderiv(shader, gl::INFO_LOG_LENGTH, &mut len);
+    let mut info_log = vec![0u8; len.max(1) as usize];
+    gl::GetShaderInfoLog(shader, len, ptr::null_mut(), info_log.as_mut_ptr() as *mut GLchar);
+    String::from_utf8_lossy(&info_log).trim_end_matches('\0').to_string()
+}
+
+pub unsafe fn program_info_log(program: u32) -> String {
+    let mut len = 0;
+    gl::GetProgramiv(program, gl::INFO_LOG_LENGTH, &mut len);
+    let mut info_log = vec![0u8; len.max(1) as usize];
+    gl::GetProgramInfoLog(program, len, ptr::null_mut(), info_log.as_mut_ptr() as *mut GLchar);
+    String::from_utf8_lossy(&info_log).trim_end_matches('\0').to_string()
+}
+
 pub unsafe fn check_compile_errors(shader: u32, type_: &str) {
     let mut success = gl::FALSE as GLint;
     let mut info_log = Vec::with_capacity(1024);
@@ -181,3 +353,29 @@ pub unsafe fn check_compile_errors(shader: u32, type_: &str) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a shader that already looked up `name` and didn't find it, so no GL
+    /// calls are needed
+    fn shader_missing(name: &str) -> Shader {
+        let shader = Shader::from_program(0);
+        shader.locations.borrow_mut().insert(name.into(), -1);
+        shader
+    }
+
+    #[test]
+    fn set_warns_about_misses_cached_by_try_set() {
+        let shader = shader_missing("heightScale");
+        unsafe {
+            // the lookup of `try_set`
+            assert_eq!(shader.lookup_location("heightScale", false), -1);
+            assert!(shader.reported_missing.borrow().is_empty());
+            assert_eq!(shader.uniform_location("heightScale"), -1);
+        }
+        assert!(shader.reported_missing.borrow().contains("heightScale"));
+        assert!(!shader.was_set("heightScale"));
+    }
+}
@@ -0,0 +1,237 @@
+#![allow(dead_code)]
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ShaderStage {
+    Vertex,
+    Fragment,
+    Geometry,
+    TessControl,
+    TessEvaluation,
+    Compute,
+}
+
+impl ShaderStage {
+    pub fn gl_type(self) -> u32 {
+        match self {
+            ShaderStage::Vertex => gl::VERTEX_SHADER,
+            ShaderStage::Fragment => gl::FRAGMENT_SHADER,
+            ShaderStage::Geometry => gl::GEOMETRY_SHADER,
+            ShaderStage::TessControl => gl::TESS_CONTROL_SHADER,
+            ShaderStage::TessEvaluation => gl::TESS_EVALUATION_SHADER,
+            ShaderStage::Compute => gl::COMPUTE_SHADER,
+        }
+    }
+}
+
+impl fmt::Display for ShaderStage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ShaderStage::Vertex => "VERTEX",
+            ShaderStage::Fragment => "FRAGMENT",
+            ShaderStage::Geometry => "GEOMETRY",
+            ShaderStage::TessControl => "TESS_CONTROL",
+            ShaderStage::TessEvaluation => "TESS_EVALUATION",
+            ShaderStage::Compute => "COMPUTE",
+        })
+    }
+}
+
+/// One message of a compile or link info log
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    /// source file the message refers to, if the driver reported a location
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    pub message: String,
+}
+
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.file, self.line) {
+            (Some(file), Some(line)) => write!(f, "{}:{}: {}", file, line, self.message),
+            (Some(file), None) => write!(f, "{}: {}", file, self.message),
+            _ => f.write_str(&self.message),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ShaderError {
+    /// a shader source file couldn't be read
+    Io {
+        path: String,
+        error: io::Error,
+    },
+    /// a malformed `#include` or an include cycle
+    Preprocess {
+        file: String,
+        line: u32,
+        message: String,
+    },
+    /// a combination of stages that can't be linked into a program, e.g. a
+    /// geometry shader without a vertex shader
+    Stages {
+        message: String,
+    },
+    Compile {
+        stage: ShaderStage,
+        log: Vec<LogEntry>,
+    },
+    Link {
+        log: Vec<LogEntry>,
+    },
+}
+
+impl fmt::Display for ShaderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let log = match self {
+            ShaderError::Io { path, error } => return write!(f, "Failed to read {}: {}", path, error),
+            ShaderError::Preprocess { file, line, message } => return write!(f, "{}:{}: {}", file, line, message),
+            ShaderError::Stages { message } => return write!(f, "ERROR::SHADER::INVALID_STAGES: {}", message),
+            ShaderError::Compile { stage, log } => {
+                writeln!(f, "ERROR::SHADER_COMPILATION_ERROR of type: {}", stage)?;
+                log
+            }
+            ShaderError::Link { log } => {
+                writeln!(f, "ERROR::PROGRAM_LINKING_ERROR")?;
+                log
+            }
+        };
+        for entry in log {
+            writeln!(f, "{}", entry)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ShaderError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ShaderError::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Splits a driver info log into entries. Understands the location formats of
+/// Mesa (`0:12(5): error: ...`), NVIDIA (`0(12) : error C0000: ...`) and
+/// AMD/Intel/Apple (`ERROR: 0:12: ...`). `file` names the source string 0;
+/// lines without a location are kept as plain messages.
+pub fn parse_info_log(log: &str, file: Option<&str>) -> Vec<LogEntry> {
+    log.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| match parse_log_line(line) {
+            Some((_source, line, message)) => LogEntry { file: file.map(String::from), line: Some(line), message },
+            None => LogEntry { file: file.map(String::from), line: None, message: line.to_string() },
+        })
+        .collect()
+}
+
+/// Returns source string number, line and message of a log line with a location
+fn parse_log_line(line: &str) -> Option<(u32, u32, String)> {
+    let (severity, line) = if line.starts_with("ERROR: ") {
+        ("error: ", &line["ERROR: ".len()..])
+    } else if line.starts_with("WARNING: ") {
+        ("warning: ", &line["WARNING: ".len()..])
+    } else {
+        ("", line)
+    };
+
+    let (source, rest) = split_number(line)?;
+    let (line_number, rest) = if rest.starts_with(':') {
+        let (line_number, rest) = split_number(&rest[1..])?;
+        // Mesa appends the column in parentheses
+        let rest = if rest.starts_with('(') { &rest[rest.find(')')? + 1..] } else { rest };
+        (line_number, rest)
+    } else if rest.starts_with('(') {
+        let (line_number, rest) = split_number(&rest[1..])?;
+        if !rest.starts_with(')') {
+            return None;
+        }
+        (line_number, &rest[1..])
+    } else {
+        return None;
+    };
+
+    let message = rest.trim_start_matches(|c| c == ' ' || c == ':').trim();
+    Some((source, line_number, format!("{}{}", severity, message)))
+}
+
+fn split_number(s: &str) -> Option<(u32, &str)> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or_else(|| s.len());
+    let number = s[..end].parse().ok()?;
+    Some((number, &s[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::preprocess;
+
+    /// one info log line per driver family with the location and message it should parse into
+    const VENDOR_LOGS: &[(&str, &str, u32, &str)] = &[
+        ("Mesa", "0:3(23): error: `common' undeclared", 3, "error: `common' undeclared"),
+        (
+            "NVIDIA",
+            "0(3) : error C1008: undefined variable \"common\"",
+            3,
+            "error C1008: undefined variable \"common\"",
+        ),
+        ("AMD", "ERROR: 0:3: 'common' : undeclared identifier", 3, "error: 'common' : undeclared identifier"),
+        (
+            "Apple",
+            "WARNING: 0:3: Overflow in implicit constant conversion, minimum range for lowp float is (-2,2)",
+            3,
+            "warning: Overflow in implicit constant conversion, minimum range for lowp float is (-2,2)",
+        ),
+    ];
+
+    #[test]
+    fn parses_vendor_log_locations() {
+        for (vendor, log, line, message) in VENDOR_LOGS {
+            let entries = parse_info_log(log, Some("light.fsh"));
+            assert_eq!(entries.len(), 1, "{}", vendor);
+            assert_eq!(entries[0].file.as_deref(), Some("light.fsh"), "{}", vendor);
+            assert_eq!(entries[0].line, Some(*line), "{}", vendor);
+            assert_eq!(entries[0].message, *message, "{}", vendor);
+        }
+    }
+
+    #[test]
+    fn keeps_lines_without_locations() {
+        let log = "0:1(1): error: syntax error\n\nerror: 1 compilation error\n  \n";
+        let entries = parse_info_log(log, None);
+        assert_eq!(entries.len(), 2);
+        assert_eq!((entries[0].file.as_deref(), entries[0].line), (None, Some(1)));
+        assert_eq!((entries[1].line, entries[1].message.as_str()), (None, "error: 1 compilation error"));
+        // parentheses and colons without line numbers aren't locations
+        for log in &["error(s) found", "0(x) : error", "0:: error", "12 errors"] {
+            assert_eq!(parse_info_log(log, None)[0].line, None, "{}", log);
+        }
+    }
+
+    #[test]
+    fn maps_vendor_logs_back_to_included_files() {
+        // expanded line 3 is line 2 of light.glsl
+        let files = [
+            ("main.fsh", "#version 330 core\n#include \"light.glsl\"\nvoid main() {}"),
+            ("light.glsl", "// lighting\nfloat light() { return common(); }"),
+        ];
+        let source = preprocess("main.fsh", &[], |path| {
+            let path = path.replace('\\', "/");
+            let found = files.iter().find(|(name, _)| *name == path);
+            found.map(|(_, source)| source.to_string()).ok_or_else(|| io::ErrorKind::NotFound.into())
+        })
+        .unwrap();
+        for (vendor, log, _, message) in VENDOR_LOGS {
+            let mut entries = parse_info_log(log, Some("main.fsh"));
+            source.map_log_locations(&mut entries);
+            assert_eq!(entries[0].to_string(), format!("light.glsl:2: {}", message), "{}", vendor);
+        }
+    }
+}
@@ -0,0 +1,44 @@
+// Shadow lookup for a `PointShadow` (src/shared/point_shadow.rs), which sets
+// all of these uniforms in `PointShadow::apply`. Include it into the lit pass.
+
+uniform samplerCube pointShadowMap;
+uniform vec3 pointShadowLightPos;
+uniform float pointShadowFarPlane;
+uniform float pointShadowBias;
+// sample a disk of offsets around the lookup direction instead of a single texel
+uniform bool pointShadowPcf;
+
+// directions to offset the lookup by, spread in all three axes so few samples end up in the same texel
+const vec3 pointShadowSampleOffsets[20] = vec3[](
+    vec3( 1,  1,  1), vec3( 1, -1,  1), vec3(-1, -1,  1), vec3(-1,  1,  1),
+    vec3( 1,  1, -1), vec3( 1, -1, -1), vec3(-1, -1, -1), vec3(-1,  1, -1),
+    vec3( 1,  1,  0), vec3( 1, -1,  0), vec3(-1, -1,  0), vec3(-1,  1,  0),
+    vec3( 1,  0,  1), vec3(-1,  0,  1), vec3( 1,  0, -1), vec3(-1,  0, -1),
+    vec3( 0,  1,  1), vec3( 0, -1,  1), vec3( 0, -1, -1), vec3( 0,  1, -1)
+);
+
+// 1.0 if the fragment is in shadow, 0.0 if it's lit, anything in between at PCF edges
+float PointShadowCalculation(vec3 fragPos, vec3 viewPos) {
+    // get vector between fragment position and light position
+    vec3 fragToLight = fragPos - pointShadowLightPos;
+    // now get current linear depth as the length between the fragment and light position
+    float currentDepth = length(fragToLight);
+    if (!pointShadowPcf) {
+        // the closest depth is stored in [0,1] range, transform it back to the original depth value
+        float closestDepth = texture(pointShadowMap, fragToLight).r * pointShadowFarPlane;
+        return currentDepth - pointShadowBias > closestDepth ? 1.0 : 0.0;
+    }
+
+    float shadow = 0.0;
+    // softer shadows further away from the viewer
+    float viewDistance = length(viewPos - fragPos);
+    float diskRadius = (1.0 + (viewDistance / pointShadowFarPlane)) / 25.0;
+    for (int i = 0; i < 20; ++i) {
+        float closestDepth = texture(pointShadowMap, fragToLight + pointShadowSampleOffsets[i] * diskRadius).r;
+        closestDepth *= pointShadowFarPlane;
+        if (currentDepth - pointShadowBias > closestDepth) {
+            shadow += 1.0;
+        }
+    }
+    return shadow / 20.0;
+}
@@ -0,0 +1,36 @@
+// Shadow lookup for a `ShadowMap` (src/shared/shadow_map.rs), which sets all
+// of these uniforms in `ShadowMap::apply`. Include it into the lit pass and
+// pass `lightSpaceMatrix * model * vec4(aPos, 1.0)` from the vertex shader.
+
+uniform sampler2D shadowMap;
+// depth bias against shadow acne: maxBias where the surface faces away from
+// the light, down to minBias where it faces the light directly
+uniform float shadowMinBias;
+uniform float shadowMaxBias;
+// percentage-closer filtering over (2 * radius + 1)^2 texels, 0 for hard shadows
+uniform int shadowPcfRadius;
+
+// 1.0 if the fragment is in shadow, 0.0 if it's lit, anything in between at PCF edges
+float ShadowCalculation(vec4 fragPosLightSpace, vec3 normal, vec3 lightDir) {
+    // perform perspective divide and transform to [0,1] range
+    vec3 projCoords = fragPosLightSpace.xyz / fragPosLightSpace.w;
+    projCoords = projCoords * 0.5 + 0.5;
+    // outside the far plane of the light's frustum: nothing can have cast a shadow
+    if (projCoords.z > 1.0) {
+        return 0.0;
+    }
+    // get depth of current fragment from light's perspective
+    float currentDepth = projCoords.z;
+    float bias = max(shadowMaxBias * (1.0 - dot(normalize(normal), normalize(lightDir))), shadowMinBias);
+
+    float shadow = 0.0;
+    vec2 texelSize = 1.0 / textureSize(shadowMap, 0);
+    for (int x = -shadowPcfRadius; x <= shadowPcfRadius; ++x) {
+        for (int y = -shadowPcfRadius; y <= shadowPcfRadius; ++y) {
+            float pcfDepth = texture(shadowMap, projCoords.xy + vec2(x, y) * texelSize).r;
+            shadow += currentDepth - bias > pcfDepth ? 1.0 : 0.0;
+        }
+    }
+    float samples = float((2 * shadowPcfRadius + 1) * (2 * shadowPcfRadius + 1));
+    return shadow / samples;
+}
@@ -0,0 +1,115 @@
+#![allow(dead_code)]
+
+use cgmath::prelude::*;
+use cgmath::{ortho, vec3, Matrix4, Point3};
+use gl;
+
+use crate::shared::{DepthAttachment, Framebuffer, FramebufferError, Shader};
+
+/// Shadows of a directional light: the scene is rendered from the light into
+/// a depth texture, then the lit pass compares each fragment's depth as seen
+/// from the light with the depth stored in the map.
+///
+/// ```ignore
+/// let depth_shader = shadow_map.begin();
+/// // draw the shadow casters, setting `model` on depth_shader
+/// shadow_map.end(window_width, window_height);
+/// shadow_map.apply(&shader, 1);
+/// // draw the scene with a shader including src/shared/shaders/shadow.glsl
+/// ```
+pub struct ShadowMap {
+    /// depth texture only, no color attachments
+    framebuffer: Framebuffer,
+    /// renders positions at location 0 with the `model` matrix into the map
+    pub depth_shader: Shader,
+    light_space_matrix: Matrix4<f32>,
+    /// bias for surfaces facing the light, see shadow.glsl
+    pub min_bias: f32,
+    /// bias for surfaces at a grazing angle to the light
+    pub max_bias: f32,
+    /// percentage-closer filtering radius in texels, 0 for hard shadows
+    pub pcf_radius: i32,
+}
+
+impl ShadowMap {
+    /// A `size` x `size` shadow map. Set the light with `look_at` before rendering.
+    pub unsafe fn new(size: i32) -> Result<ShadowMap, FramebufferError> {
+        let framebuffer = Framebuffer::builder(size, size).depth(DepthAttachment::DepthTexture).build()?;
+        let depth_map = framebuffer.depth_texture().unwrap();
+        gl::BindTexture(gl::TEXTURE_2D, depth_map);
+        // interpolating depth values makes no sense, PCF does the smoothing
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::NEAREST as i32);
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::NEAREST as i32);
+        // everything outside of the light's frustum is lit: sample the maximum depth there
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_BORDER as i32);
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_BORDER as i32);
+        let border_color = [1.0f32, 1.0, 1.0, 1.0];
+        gl::TexParameterfv(gl::TEXTURE_2D, gl::TEXTURE_BORDER_COLOR, border_color.as_ptr());
+        gl::BindTexture(gl::TEXTURE_2D, 0);
+
+        let depth_shader = Shader::new("src/shared/shaders/shadow_depth.vsh", "src/shared/shaders/shadow_depth.fsh");
+        Ok(ShadowMap {
+            framebuffer,
+            depth_shader,
+            light_space_matrix: Matrix4::identity(),
+            min_bias: 0.005,
+            max_bias: 0.05,
+            pcf_radius: 1,
+        })
+    }
+
+    /// Places the light at `position`, shining towards `target`. Its parallel
+    /// rays cover a square of `half_size` around the line between the two and
+    /// the depths from `near` to `far` along it; everything casting or
+    /// receiving shadows has to be inside. The smaller the frustum, the more
+    /// detailed the shadows.
+    pub fn look_at(&mut self, position: Point3<f32>, target: Point3<f32>, half_size: f32, near: f32, far: f32) {
+        let direction = (target - position).normalize();
+        // looking straight up or down, any other up vector will do
+        let up = if direction.y.abs() > 0.999 { vec3(0.0, 0.0, 1.0) } else { vec3(0.0, 1.0, 0.0) };
+        let projection = ortho(-half_size, half_size, -half_size, half_size, near, far);
+        self.light_space_matrix = projection * Matrix4::look_at(position, target, up);
+    }
+
+    /// Transforms world space into the light's clip space
+    pub fn light_space_matrix(&self) -> Matrix4<f32> {
+        self.light_space_matrix
+    }
+
+    pub fn depth_texture(&self) -> u32 {
+        self.framebuffer.depth_texture().unwrap()
+    }
+
+    pub fn size(&self) -> i32 {
+        self.framebuffer.width()
+    }
+
+    /// Binds and clears the map for the depth pass. Returns the depth shader,
+    /// ready to draw with after setting `model`.
+    pub unsafe fn begin(&self) -> &Shader {
+        self.framebuffer.bind();
+        gl::Clear(gl::DEPTH_BUFFER_BIT);
+        self.depth_shader.use_program();
+        self.depth_shader.set("lightSpaceMatrix", &self.light_space_matrix);
+        &self.depth_shader
+    }
+
+    /// Ends the depth pass, going back to the window's framebuffer of the given size
+    pub unsafe fn end(&self, width: i32, height: i32) {
+        Framebuffer::bind_default(width, height);
+    }
+
+    /// Binds the map to texture unit `unit` and sets the uniforms of
+    /// shadow.glsl and `lightSpaceMatrix` on `shader`, which is made current
+    pub unsafe fn apply(&self, shader: &Shader, unit: u32) {
+        shader.use_program();
+        shader.set("lightSpaceMatrix", &self.light_space_matrix);
+        shader.set("shadowMap", &(unit as i32));
+        shader.set("shadowMinBias", &self.min_bias);
+        shader.set("shadowMaxBias", &self.max_bias);
+        shader.set("shadowPcfRadius", &self.pcf_radius);
+        gl::ActiveTexture(gl::TEXTURE0 + unit);
+        gl::BindTexture(gl::TEXTURE_2D, self.depth_texture());
+        gl::ActiveTexture(gl::TEXTURE0);
+    }
+}
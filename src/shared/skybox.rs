@@ -0,0 +1,139 @@
+#![allow(dead_code)]
+
+use std::mem;
+use std::os::raw::c_void;
+use std::ptr;
+
+use cgmath::{vec4, Matrix4};
+use gl;
+use gl::types::*;
+
+use crate::shared::{load_cubemap, Shader};
+
+/// Draws a cubemap around the camera, behind everything else. Render it last,
+/// after the opaque geometry, so the depth test discards the hidden fragments.
+///
+/// The shader gets the cube's corners as `aPos` at location 0, the `projection`
+/// and `view` matrices and the cubemap as `samplerCube skybox`. It's expected
+/// to write `pos.xyww` to `gl_Position`, putting the skybox at maximum depth.
+pub struct Skybox {
+    pub shader: Shader,
+    texture: u32,
+    vao: u32,
+    vbo: u32,
+}
+
+impl Skybox {
+    /// Takes ownership of `texture`, a cubemap as returned by `load_cubemap`
+    pub unsafe fn new(texture: u32, shader: Shader) -> Skybox {
+        shader.use_program();
+        shader.set("skybox", &0);
+        let (vao, vbo) = create_cube();
+        Skybox { shader, texture, vao, vbo }
+    }
+
+    /// Loads the faces with `load_cubemap`, see `CUBEMAP_FACES` for their order
+    pub unsafe fn from_faces(faces: &[&str; 6], shader: Shader) -> Skybox {
+        Skybox::new(load_cubemap(faces), shader)
+    }
+
+    /// the cubemap, e.g. for environment mapping
+    pub fn texture(&self) -> u32 {
+        self.texture
+    }
+
+    /// Draws the skybox with the translation removed from `view`, so it stays
+    /// around the camera however far it moves. Leaves texture unit 0 bound to
+    /// the cubemap.
+    pub unsafe fn draw(&self, view: &Matrix4<f32>, projection: &Matrix4<f32>) {
+        let mut view = *view;
+        view.w = vec4(0.0, 0.0, 0.0, 1.0);
+
+        // change depth function so depth test passes when values are equal to depth buffer's content
+        gl::DepthFunc(gl::LEQUAL);
+        self.shader.use_program();
+        self.shader.set("view", &view);
+        self.shader.set("projection", projection);
+        gl::BindVertexArray(self.vao);
+        gl::ActiveTexture(gl::TEXTURE0);
+        gl::BindTexture(gl::TEXTURE_CUBE_MAP, self.texture);
+        gl::DrawArrays(gl::TRIANGLES, 0, 36);
+        gl::BindVertexArray(0);
+        gl::DepthFunc(gl::LESS); // set depth function back to default
+    }
+}
+
+impl Drop for Skybox {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteVertexArrays(1, &self.vao);
+            gl::DeleteBuffers(1, &self.vbo);
+            gl::DeleteTextures(1, &self.texture);
+        }
+    }
+}
+
+/// A unit cube around the origin, only positions
+unsafe fn create_cube() -> (u32, u32) {
+    #[rustfmt::skip]
+    let vertices: [f32; 108] = [
+        // positions
+        -1.0,  1.0, -1.0,
+        -1.0, -1.0, -1.0,
+         1.0, -1.0, -1.0,
+         1.0, -1.0, -1.0,
+         1.0,  1.0, -1.0,
+        -1.0,  1.0, -1.0,
+
+        -1.0, -1.0,  1.0,
+        -1.0, -1.0, -1.0,
+        -1.0,  1.0, -1.0,
+        -1.0,  1.0, -1.0,
+        -1.0,  1.0,  1.0,
+        -1.0, -1.0,  1.0,
+
+         1.0, -1.0, -1.0,
+         1.0, -1.0,  1.0,
+         1.0,  1.0,  1.0,
+         1.0,  1.0,  1.0,
+         1.0,  1.0, -1.0,
+         1.0, -1.0, -1.0,
+
+        -1.0, -1.0,  1.0,
+        -1.0,  1.0,  1.0,
+         1.0,  1.0,  1.0,
+         1.0,  1.0,  1.0,
+         1.0, -1.0,  1.0,
+        -1.0, -1.0,  1.0,
+
+        -1.0,  1.0, -1.0,
+         1.0,  1.0, -1.0,
+         1.0,  1.0,  1.0,
+         1.0,  1.0,  1.0,
+        -1.0,  1.0,  1.0,
+        -1.0,  1.0, -1.0,
+
+        -1.0, -1.0, -1.0,
+        -1.0, -1.0,  1.0,
+         1.0, -1.0, -1.0,
+         1.0, -1.0, -1.0,
+        -1.0, -1.0,  1.0,
+         1.0, -1.0,  1.0,
+    ];
+
+    let (mut vao, mut vbo) = (0, 0);
+    gl::GenVertexArrays(1, &mut vao);
+    gl::GenBuffers(1, &mut vbo);
+    gl::BindVertexArray(vao);
+    gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+    gl::BufferData(
+        gl::ARRAY_BUFFER,
+        mem::size_of_val(&vertices) as GLsizeiptr,
+        vertices.as_ptr() as *const c_void,
+        gl::STATIC_DRAW,
+    );
+    gl::EnableVertexAttribArray(0);
+    gl::VertexAttribPointer(0, 3, gl::FLOAT, gl::FALSE, 3 * mem::size_of::<GLfloat>() as GLsizei, ptr::null());
+    gl::BindVertexArray(0);
+    (vao, vbo)
+}
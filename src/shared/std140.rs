@@ -0,0 +1,405 @@
+#![allow(dead_code)]
+
+use cgmath::{Matrix3, Matrix4, Point3, Vector2, Vector3, Vector4};
+
+/// A value with a known std140 layout, i.e. one that can be stored in a
+/// `layout (std140) uniform` block, see `UniformBuffer`.
+///
+/// The rules in short: scalars take 4 bytes, `vec2` is 8 byte aligned, `vec3`
+/// and `vec4` are 16 byte aligned, matrices are arrays of column vectors and
+/// every array element and struct is padded to a multiple of 16 bytes.
+///
+/// Structs get an implementation from `glsl_struct!`.
+pub trait Std140 {
+    /// base alignment in bytes
+    const ALIGNMENT: usize;
+    /// size in bytes, not including padding up to the next member
+    const SIZE: usize;
+
+    /// Writes the value to the start of `out`, which is at least `SIZE` bytes long
+    fn write_std140(&self, out: &mut [u8]);
+
+    fn to_std140_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0u8; Self::SIZE];
+        self.write_std140(&mut bytes);
+        bytes
+    }
+}
+
+/// Like `Std140`, but for the std430 layout of shader storage blocks. Same as
+/// std140, except that arrays and structs are only padded to the alignment of
+/// their members instead of 16 bytes.
+pub trait Std430 {
+    const ALIGNMENT: usize;
+    const SIZE: usize;
+
+    fn write_std430(&self, out: &mut [u8]);
+
+    fn to_std430_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0u8; Self::SIZE];
+        self.write_std430(&mut bytes);
+        bytes
+    }
+}
+
+/// Rounds `offset` up to the next multiple of `alignment`
+pub const fn align_to(offset: usize, alignment: usize) -> usize {
+    (offset + alignment - 1) / alignment * alignment
+}
+
+/// Offset of member `index` of a struct, given `(alignment, size)` of all its
+/// members in declaration order
+pub const fn member_offset(members: &[(usize, usize)], index: usize) -> usize {
+    let mut offset = 0;
+    let mut i = 0;
+    while i < index {
+        offset = align_to(offset, members[i].0) + members[i].1;
+        i += 1;
+    }
+    align_to(offset, members[index].0)
+}
+
+/// End of the last member of a struct, see `member_offset`
+pub const fn members_end(members: &[(usize, usize)]) -> usize {
+    if members.is_empty() {
+        0
+    } else {
+        member_offset(members, members.len() - 1) + members[members.len() - 1].1
+    }
+}
+
+/// Largest alignment of the members of a struct, see `member_offset`
+pub const fn max_alignment(members: &[(usize, usize)]) -> usize {
+    let mut alignment = 1;
+    let mut i = 0;
+    while i < members.len() {
+        if members[i].0 > alignment {
+            alignment = members[i].0;
+        }
+        i += 1;
+    }
+    alignment
+}
+
+/// Position of `name` in `names`, used by `glsl_struct!` to look up offsets
+pub const fn field_index(names: &[&str], name: &str) -> usize {
+    let mut i = 0;
+    while i < names.len() {
+        let (a, b) = (names[i].as_bytes(), name.as_bytes());
+        if a.len() == b.len() {
+            let mut j = 0;
+            while j < a.len() && a[j] == b[j] {
+                j += 1;
+            }
+            if j == a.len() {
+                return i;
+            }
+        }
+        i += 1;
+    }
+    // not reached, `glsl_struct!` only looks up its own field names
+    names.len()
+}
+
+macro_rules! impl_layout_writer {
+    ($writer:ident, $layout:ident, $write:ident) => {
+        /// Writes the members of a struct one after another, inserting the
+        /// padding in between. Used by `glsl_struct!`, but also handy for
+        /// implementing the layout traits by hand.
+        pub struct $writer<'a> {
+            out: &'a mut [u8],
+            offset: usize,
+        }
+
+        impl<'a> $writer<'a> {
+            pub fn new(out: &'a mut [u8]) -> $writer<'a> {
+                $writer { out, offset: 0 }
+            }
+
+            /// Writes the next member and returns its offset
+            pub fn write<T: $layout>(&mut self, value: &T) -> usize {
+                let offset = align_to(self.offset, T::ALIGNMENT);
+                value.$write(&mut self.out[offset..offset + T::SIZE]);
+                self.offset = offset + T::SIZE;
+                offset
+            }
+
+            /// bytes written so far
+            pub fn offset(&self) -> usize {
+                self.offset
+            }
+        }
+    };
+}
+
+impl_layout_writer!(Std140Writer, Std140, write_std140);
+impl_layout_writer!(Std430Writer, Std430, write_std430);
+
+fn write_floats(out: &mut [u8], values: &[f32]) {
+    for (chunk, value) in out.chunks_exact_mut(4).zip(values) {
+        chunk.copy_from_slice(&value.to_ne_bytes());
+    }
+}
+
+macro_rules! impl_std140_scalar {
+    ($($ty:ty),*) => {
+        $(
+            impl Std140 for $ty {
+                const ALIGNMENT: usize = 4;
+                const SIZE: usize = 4;
+
+                fn write_std140(&self, out: &mut [u8]) {
+                    out[..4].copy_from_slice(&self.to_ne_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_std140_scalar!(f32, i32, u32);
+
+/// GLSL bools are 4 bytes in a uniform block
+impl Std140 for bool {
+    const ALIGNMENT: usize = 4;
+    const SIZE: usize = 4;
+
+    fn write_std140(&self, out: &mut [u8]) {
+        (*self as u32).write_std140(out);
+    }
+}
+
+impl Std140 for Vector2<f32> {
+    const ALIGNMENT: usize = 8;
+    const SIZE: usize = 8;
+
+    fn write_std140(&self, out: &mut [u8]) {
+        write_floats(out, &[self.x, self.y]);
+    }
+}
+
+impl Std140 for Vector3<f32> {
+    const ALIGNMENT: usize = 16;
+    const SIZE: usize = 12;
+
+    fn write_std140(&self, out: &mut [u8]) {
+        write_floats(out, &[self.x, self.y, self.z]);
+    }
+}
+
+impl Std140 for Point3<f32> {
+    const ALIGNMENT: usize = 16;
+    const SIZE: usize = 12;
+
+    fn write_std140(&self, out: &mut [u8]) {
+        write_floats(out, &[self.x, self.y, self.z]);
+    }
+}
+
+impl Std140 for Vector4<f32> {
+    const ALIGNMENT: usize = 16;
+    const SIZE: usize = 16;
+
+    fn write_std140(&self, out: &mut [u8]) {
+        write_floats(out, &[self.x, self.y, self.z, self.w]);
+    }
+}
+
+/// three columns, each padded to a vec4
+impl Std140 for Matrix3<f32> {
+    const ALIGNMENT: usize = 16;
+    const SIZE: usize = 48;
+
+    fn write_std140(&self, out: &mut [u8]) {
+        for (column, out) in [self.x, self.y, self.z].iter().zip(out.chunks_exact_mut(16)) {
+            column.write_std140(out);
+        }
+    }
+}
+
+impl Std140 for Matrix4<f32> {
+    const ALIGNMENT: usize = 16;
+    const SIZE: usize = 64;
+
+    fn write_std140(&self, out: &mut [u8]) {
+        for (column, out) in [self.x, self.y, self.z, self.w].iter().zip(out.chunks_exact_mut(16)) {
+            column.write_std140(out);
+        }
+    }
+}
+
+/// Scalars, vectors and matrices are laid out the same in both layouts
+macro_rules! impl_std430_as_std140 {
+    ($($ty:ty),*) => {
+        $(
+            impl Std430 for $ty {
+                const ALIGNMENT: usize = <$ty as Std140>::ALIGNMENT;
+                const SIZE: usize = <$ty as Std140>::SIZE;
+
+                fn write_std430(&self, out: &mut [u8]) {
+                    self.write_std140(out);
+                }
+            }
+        )*
+    };
+}
+
+impl_std430_as_std140!(f32, i32, u32, bool, Vector2<f32>, Vector3<f32>, Point3<f32>, Vector4<f32>);
+impl_std430_as_std140!(Matrix3<f32>, Matrix4<f32>);
+
+/// std140 arrays have every element padded to 16 bytes, even scalars; std430
+/// ones only to the element's alignment
+macro_rules! impl_layout_array {
+    ($($n:expr),*) => {
+        $(
+            impl<T: Std140> Std140 for [T; $n] {
+                const ALIGNMENT: usize = align_to(T::ALIGNMENT, 16);
+                const SIZE: usize = align_to(T::SIZE, Self::ALIGNMENT) * $n;
+
+                fn write_std140(&self, out: &mut [u8]) {
+                    let stride = align_to(T::SIZE, Self::ALIGNMENT);
+                    for (element, out) in self.iter().zip(out.chunks_mut(stride)) {
+                        element.write_std140(out);
+                    }
+                }
+            }
+
+            impl<T: Std430> Std430 for [T; $n] {
+                const ALIGNMENT: usize = T::ALIGNMENT;
+                const SIZE: usize = align_to(T::SIZE, T::ALIGNMENT) * $n;
+
+                fn write_std430(&self, out: &mut [u8]) {
+                    let stride = align_to(T::SIZE, T::ALIGNMENT);
+                    for (element, out) in self.iter().zip(out.chunks_mut(stride)) {
+                        element.write_std430(out);
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_layout_array!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 32, 64);
+
+// the GLSL rules for the basic types
+// ----------------------------------
+const_assert!(<Vector3<f32> as Std140>::ALIGNMENT == 16 && <Vector3<f32> as Std140>::SIZE == 12);
+const_assert!(<Matrix3<f32> as Std140>::SIZE == 48);
+// float[4]: 16 byte stride in std140, tightly packed in std430
+const_assert!(<[f32; 4] as Std140>::SIZE == 64 && <[f32; 4] as Std430>::SIZE == 16);
+// vec3[2]: padded to vec4 in both
+const_assert!(<[Vector3<f32>; 2] as Std140>::SIZE == 32 && <[Vector3<f32>; 2] as Std430>::SIZE == 32);
+const_assert!(<[Vector2<f32>; 3] as Std140>::SIZE == 48 && <[Vector2<f32>; 3] as Std430>::SIZE == 24);
+
+// and a struct mixing all of them, offsets worked out by hand from the spec
+// -------------------------------------------------------------------------
+glsl_struct! {
+    struct LayoutExample {
+        a: f32,
+        b: Vector2<f32>,
+        c: Vector3<f32>,
+        d: f32,
+        e: [f32; 2],
+        f: Matrix3<f32>,
+        g: Inner,
+        h: bool,
+    }
+}
+
+glsl_struct! {
+    struct Inner {
+        x: f32,
+        y: Vector2<f32>,
+    }
+}
+
+const_assert!(Inner::STD140_OFFSETS[1].1 == 8 && <Inner as Std140>::SIZE == 16 && <Inner as Std140>::ALIGNMENT == 16);
+const_assert!(<Inner as Std430>::SIZE == 16 && <Inner as Std430>::ALIGNMENT == 8);
+
+// std140: a 0, b 8, c 16, d 28, e 32 (stride 16), f 64, g 112, h 128
+const_assert!(LayoutExample::STD140_OFFSETS[1].1 == 8 && LayoutExample::STD140_OFFSETS[2].1 == 16);
+const_assert!(LayoutExample::STD140_OFFSETS[3].1 == 28 && LayoutExample::STD140_OFFSETS[4].1 == 32);
+const_assert!(LayoutExample::STD140_OFFSETS[5].1 == 64 && LayoutExample::STD140_OFFSETS[6].1 == 112);
+const_assert!(LayoutExample::STD140_OFFSETS[7].1 == 128 && <LayoutExample as Std140>::SIZE == 144);
+
+// std430: same up to d, e 32 (stride 4), f 48, g 96 (8 byte aligned), h 112
+const_assert!(LayoutExample::STD430_OFFSETS[3].1 == 28 && LayoutExample::STD430_OFFSETS[4].1 == 32);
+const_assert!(LayoutExample::STD430_OFFSETS[5].1 == 48 && LayoutExample::STD430_OFFSETS[6].1 == 96);
+const_assert!(LayoutExample::STD430_OFFSETS[7].1 == 112 && <LayoutExample as Std430>::SIZE == 128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout_example() -> LayoutExample {
+        LayoutExample {
+            a: 1.0,
+            b: Vector2::new(2.0, 3.0),
+            c: Vector3::new(4.0, 5.0, 6.0),
+            d: 7.0,
+            e: [8.0, 9.0],
+            f: Matrix3::new(10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0, 17.0, 18.0),
+            g: Inner { x: 19.0, y: Vector2::new(20.0, 21.0) },
+            h: true,
+        }
+    }
+
+    /// `size` zero bytes with the given `(offset, floats)` written over them,
+    /// so that any padding byte written by mistake shows up as a difference
+    fn expected_bytes(size: usize, values: &[(usize, &[f32])], h_offset: usize) -> Vec<u8> {
+        let mut bytes = vec![0u8; size];
+        for (offset, floats) in values {
+            write_floats(&mut bytes[*offset..], floats);
+        }
+        bytes[h_offset..h_offset + 4].copy_from_slice(&1u32.to_ne_bytes());
+        bytes
+    }
+
+    #[test]
+    fn layout_example_std140_bytes() {
+        let expected = expected_bytes(
+            144,
+            &[
+                (0, &[1.0]),
+                (8, &[2.0, 3.0]),
+                (16, &[4.0, 5.0, 6.0]),
+                (28, &[7.0]),
+                // array elements padded to 16 bytes
+                (32, &[8.0]),
+                (48, &[9.0]),
+                // mat3 columns padded to vec4
+                (64, &[10.0, 11.0, 12.0]),
+                (80, &[13.0, 14.0, 15.0]),
+                (96, &[16.0, 17.0, 18.0]),
+                (112, &[19.0]),
+                (120, &[20.0, 21.0]),
+            ],
+            128,
+        );
+        // bytes 132..144 are the trailing padding of the struct
+        assert_eq!(layout_example().to_std140_bytes(), expected);
+    }
+
+    #[test]
+    fn layout_example_std430_bytes() {
+        let expected = expected_bytes(
+            128,
+            &[
+                (0, &[1.0]),
+                (8, &[2.0, 3.0]),
+                (16, &[4.0, 5.0, 6.0]),
+                (28, &[7.0]),
+                // tightly packed float array
+                (32, &[8.0, 9.0]),
+                // mat3 columns still padded to vec4
+                (48, &[10.0, 11.0, 12.0]),
+                (64, &[13.0, 14.0, 15.0]),
+                (80, &[16.0, 17.0, 18.0]),
+                (96, &[19.0]),
+                (104, &[20.0, 21.0]),
+            ],
+            112,
+        );
+        // bytes 116..128 are the trailing padding of the struct
+        assert_eq!(layout_example().to_std430_bytes(), expected);
+    }
+}
@@ -0,0 +1,136 @@
+#![allow(dead_code)]
+
+use cgmath::prelude::*;
+use cgmath::{vec3, Vector3};
+
+use crate::shared::Vertex;
+
+/// Below this the texture coordinates of a triangle don't span an area, and
+/// it can't tell which way the tangent goes
+const MIN_UV_AREA: f32 = 1e-12;
+
+/// Fills in `tangent` and `bitangent` of `vertices` from their positions and
+/// texture coordinates, for the triangle list `indices`: the tangent points
+/// along increasing u, the bitangent along increasing v.
+///
+/// Follows the MikkTSpace conventions as far as per-vertex averaging allows:
+/// the tangents of the triangles around a vertex are summed up weighted by
+/// area, orthogonalized against the vertex normal (Gram-Schmidt) and
+/// normalized, and the bitangent is `cross(normal, tangent)`, flipped where
+/// the texture is mirrored. Vertices shared by mirrored and unmirrored
+/// triangles get the majority's handedness; exporters split those anyway.
+/// The normals have to be set already.
+pub fn compute_tangents(vertices: &mut [Vertex], indices: &[u32]) {
+    assert_eq!(indices.len() % 3, 0, "ERROR::TANGENTS: {} indices don't make up whole triangles", indices.len());
+    let mut tangents = vec![Vector3::zero(); vertices.len()];
+    let mut bitangents = vec![Vector3::zero(); vertices.len()];
+
+    for triangle in indices.chunks(3) {
+        let (i0, i1, i2) = (triangle[0] as usize, triangle[1] as usize, triangle[2] as usize);
+        let (v0, v1, v2) = (&vertices[i0], &vertices[i1], &vertices[i2]);
+        let (edge1, edge2) = (v1.position - v0.position, v2.position - v0.position);
+        let (delta_uv1, delta_uv2) = (v1.tex_coords - v0.tex_coords, v2.tex_coords - v0.tex_coords);
+
+        let uv_area = delta_uv1.x * delta_uv2.y - delta_uv2.x * delta_uv1.y;
+        if uv_area.abs() < MIN_UV_AREA {
+            continue;
+        }
+        // solve edge = delta_u * tangent + delta_v * bitangent for both edges
+        let tangent = (edge1 * delta_uv2.y - edge2 * delta_uv1.y) / uv_area;
+        let bitangent = (edge2 * delta_uv1.x - edge1 * delta_uv2.x) / uv_area;
+        // larger triangles count more when summing up
+        let weight = edge1.cross(edge2).magnitude();
+        if weight == 0.0 {
+            continue;
+        }
+        let (tangent, bitangent) = (tangent.normalize_to(weight), bitangent.normalize_to(weight));
+        for &i in &[i0, i1, i2] {
+            tangents[i] += tangent;
+            bitangents[i] += bitangent;
+        }
+    }
+
+    for (vertex, (tangent, bitangent)) in vertices.iter_mut().zip(tangents.into_iter().zip(bitangents)) {
+        let normal = vertex.normal;
+        // Gram-Schmidt: remove the part along the normal
+        let mut tangent = tangent - normal * normal.dot(tangent);
+        if tangent.magnitude2() < MIN_UV_AREA {
+            // not part of any triangle with usable texture coordinates
+            tangent = any_perpendicular(normal);
+        }
+        vertex.tangent = tangent.normalize();
+        // mirrored texture coordinates flip the bitangent
+        let handedness = if normal.cross(vertex.tangent).dot(bitangent) < 0.0 { -1.0 } else { 1.0 };
+        vertex.bitangent = normal.cross(vertex.tangent) * handedness;
+    }
+}
+
+/// Some direction perpendicular to `normal`
+fn any_perpendicular(normal: Vector3<f32>) -> Vector3<f32> {
+    // any axis that's not (almost) parallel to the normal
+    let axis = if normal.x.abs() < 0.9 { vec3(1.0, 0.0, 0.0) } else { vec3(0.0, 1.0, 0.0) };
+    axis - normal * normal.dot(axis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cgmath::{vec2, Vector2};
+
+    fn vertex(position: Vector3<f32>, tex_coords: Vector2<f32>) -> Vertex {
+        Vertex { position, normal: vec3(0.0, 0.0, 1.0), tex_coords, ..Vertex::default() }
+    }
+
+    /// a unit quad in the XY plane facing +Z, with u running along `u_direction` of x
+    fn quad(u_direction: f32) -> Vec<Vertex> {
+        let u = |x: f32| if u_direction > 0.0 { x } else { 1.0 - x };
+        vec![
+            vertex(vec3(0.0, 0.0, 0.0), vec2(u(0.0), 0.0)),
+            vertex(vec3(1.0, 0.0, 0.0), vec2(u(1.0), 0.0)),
+            vertex(vec3(1.0, 1.0, 0.0), vec2(u(1.0), 1.0)),
+            vertex(vec3(0.0, 1.0, 0.0), vec2(u(0.0), 1.0)),
+        ]
+    }
+
+    fn assert_near(actual: Vector3<f32>, expected: Vector3<f32>) {
+        assert!((actual - expected).magnitude() < 1e-5, "expected {:?}, got {:?}", expected, actual);
+    }
+
+    #[test]
+    fn quad_tangent_space_follows_the_texture_coordinates() {
+        let mut vertices = quad(1.0);
+        compute_tangents(&mut vertices, &[0, 1, 2, 0, 2, 3]);
+        for vertex in &vertices {
+            assert_near(vertex.tangent, vec3(1.0, 0.0, 0.0));
+            assert_near(vertex.bitangent, vec3(0.0, 1.0, 0.0));
+        }
+    }
+
+    #[test]
+    fn mirrored_u_flips_the_tangent() {
+        let mut vertices = quad(-1.0);
+        compute_tangents(&mut vertices, &[0, 1, 2, 0, 2, 3]);
+        for vertex in &vertices {
+            assert_near(vertex.tangent, vec3(-1.0, 0.0, 0.0));
+            // v still runs along +Y
+            assert_near(vertex.bitangent, vec3(0.0, 1.0, 0.0));
+        }
+    }
+
+    #[test]
+    fn degenerate_texture_coordinates_give_no_nans() {
+        let mut vertices = vec![
+            vertex(vec3(0.0, 0.0, 0.0), vec2(0.5, 0.5)),
+            vertex(vec3(1.0, 0.0, 0.0), vec2(0.5, 0.5)),
+            vertex(vec3(0.0, 1.0, 0.0), vec2(0.5, 0.5)),
+        ];
+        compute_tangents(&mut vertices, &[0, 1, 2]);
+        for vertex in &vertices {
+            for value in &[vertex.tangent, vertex.bitangent] {
+                assert!(value.x.is_finite() && value.y.is_finite() && value.z.is_finite(), "{:?}", value);
+                assert!((value.magnitude() - 1.0).abs() < 1e-5);
+                assert!(value.dot(vertex.normal).abs() < 1e-5);
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+#![allow(dead_code)]
+
+use std::cmp::Ordering;
+
+use cgmath::prelude::*;
+use cgmath::{Point3, Vector3};
+use gl;
+
+use crate::shared::{create_quad, Framebuffer, FramebufferError, Shader};
+
+/// Transparent objects to draw this frame. Blending only gives the right
+/// result if whatever is behind an object has been drawn before it, so the
+/// queue hands out its items back-to-front as seen from the camera.
+///
+/// ```ignore
+/// queue.clear();
+/// for window in &windows {
+///     queue.push(window.position, window);
+/// }
+/// // draw all opaque objects first
+/// queue.draw(camera.position, |window| { /* draw it */ });
+/// ```
+pub struct TransparentQueue<T> {
+    items: Vec<(Vector3<f32>, T)>,
+}
+
+impl<T> Default for TransparentQueue<T> {
+    fn default() -> Self {
+        TransparentQueue { items: Vec::new() }
+    }
+}
+
+impl<T> TransparentQueue<T> {
+    pub fn new() -> TransparentQueue<T> {
+        TransparentQueue::default()
+    }
+
+    /// Queues `item`, sorted by the distance of `position` (in world space) to the camera
+    pub fn push(&mut self, position: Vector3<f32>, item: T) {
+        self.items.push((position, item));
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Sorts the items from the farthest to the nearest to `eye`. Items at
+    /// the same distance keep the order they were pushed in.
+    pub fn sort(&mut self, eye: Point3<f32>) {
+        let eye = eye.to_vec();
+        self.items.sort_by(|(a, _), (b, _)| {
+            // NaN positions compare equal rather than panicking
+            (b - eye).magnitude2().partial_cmp(&(a - eye).magnitude2()).unwrap_or(Ordering::Equal)
+        });
+    }
+
+    /// The items in their current order, back-to-front after `sort`
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.items.iter().map(|(_, item)| item)
+    }
+
+    /// Sorts the items for `eye` and calls `draw` on each of them, farthest first
+    pub fn draw<F: FnMut(&T)>(&mut self, eye: Point3<f32>, draw: F) {
+        self.sort(eye);
+        self.iter().for_each(draw);
+    }
+}
+
+/// Weighted blended order-independent transparency (McGuire and Bavoil 2013):
+/// instead of sorting, every transparent fragment is added to a weighted
+/// average, with weights favoring fragments that are nearer and more opaque.
+/// Approximate, but needs no sorting and handles intersecting geometry.
+///
+/// Transparent shaders have to write two outputs instead of the color:
+///
+/// ```glsl
+/// layout (location = 0) out vec4 accum;
+/// layout (location = 1) out float weight;
+/// ...
+/// float w = clamp(pow(min(1.0, color.a * 10.0) + 0.01, 3.0) * 1e8 * pow(1.0 - gl_FragCoord.z * 0.9, 3.0), 1e-2, 3e3);
+/// accum = vec4(color.rgb * color.a * w, color.a);
+/// weight = color.a * w;
+/// ```
+///
+/// The blend function stores the product of `1 - alpha` in `accum.a`, as
+/// OpenGL 3.3 has no per attachment blend functions.
+pub struct WeightedBlendedOit {
+    /// accumulation (RGBA16F) and weight (R16F) textures, plus a copy of the
+    /// opaque scene's depth to test against
+    target: Framebuffer,
+    composite: Shader,
+    quad_vao: u32,
+    quad_vbo: u32,
+}
+
+impl WeightedBlendedOit {
+    /// For a window of the given framebuffer size
+    pub unsafe fn new(width: i32, height: i32) -> Result<WeightedBlendedOit, FramebufferError> {
+        let target = Framebuffer::builder(width, height)
+            .color_texture(gl::RGBA16F)
+            .color_texture(gl::R16F)
+            .depth_stencil_renderbuffer()
+            .build()?;
+        let composite = Shader::new("src/shared/shaders/post_process.vsh", "src/shared/shaders/oit_composite.fsh");
+        composite.use_program();
+        composite.set("accumulation", &0);
+        composite.set("weights", &1);
+        let (quad_vao, quad_vbo) = create_quad();
+        Ok(WeightedBlendedOit { target, composite, quad_vao, quad_vbo })
+    }
+
+    /// Resizes the accumulation targets along with the window
+    pub unsafe fn handle_event(&mut self, event: &glfw::WindowEvent) -> Result<(), FramebufferError> {
+        self.target.handle_event(event)
+    }
+
+    /// Call after drawing the opaque geometry into the window: copies the
+    /// depth buffer of the window, which has to be `width` x `height` and 24
+    /// bit depth + 8 bit stencil (the GLFW default, without multisampling),
+    /// and binds the accumulation targets with blending set up for them.
+    /// Then draw the transparent geometry, in any order.
+    pub unsafe fn begin(&self, width: i32, height: i32) {
+        gl::BindFramebuffer(gl::READ_FRAMEBUFFER, 0);
+        gl::BindFramebuffer(gl::DRAW_FRAMEBUFFER, self.target.id);
+        gl::BlitFramebuffer(0, 0, width, height, 0, 0, width, height, gl::DEPTH_BUFFER_BIT, gl::NEAREST);
+
+        self.target.bind();
+        gl::ClearBufferfv(gl::COLOR, 0, [0.0f32, 0.0, 0.0, 1.0].as_ptr());
+        gl::ClearBufferfv(gl::COLOR, 1, [0.0f32; 4].as_ptr());
+
+        // test against the opaque geometry, but don't let transparent fragments hide each other
+        gl::Enable(gl::DEPTH_TEST);
+        gl::DepthMask(gl::FALSE);
+        gl::Enable(gl::BLEND);
+        // color and weight are summed up, alpha multiplies the revealage: dst * (1 - src)
+        gl::BlendFuncSeparate(gl::ONE, gl::ONE, gl::ZERO, gl::ONE_MINUS_SRC_ALPHA);
+    }
+
+    /// Blends the averaged transparent colors over the window's framebuffer,
+    /// which is bound afterwards. Restores depth writes and the usual
+    /// `SRC_ALPHA`, `ONE_MINUS_SRC_ALPHA` blend function.
+    pub unsafe fn composite(&self, width: i32, height: i32) {
+        Framebuffer::bind_default(width, height);
+        gl::DepthMask(gl::TRUE);
+        gl::Disable(gl::DEPTH_TEST);
+        gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
+
+        self.composite.use_program();
+        gl::ActiveTexture(gl::TEXTURE0);
+        gl::BindTexture(gl::TEXTURE_2D, self.target.color_texture(0));
+        gl::ActiveTexture(gl::TEXTURE1);
+        gl::BindTexture(gl::TEXTURE_2D, self.target.color_texture(1));
+        gl::BindVertexArray(self.quad_vao);
+        gl::DrawArrays(gl::TRIANGLES, 0, 6);
+        gl::BindVertexArray(0);
+
+        gl::ActiveTexture(gl::TEXTURE0);
+        gl::Enable(gl::DEPTH_TEST);
+    }
+}
+
+impl Drop for WeightedBlendedOit {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteVertexArrays(1, &self.quad_vao);
+            gl::DeleteBuffers(1, &self.quad_vbo);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+#![allow(dead_code)]
+
+use cgmath::{Matrix3, Matrix4, Point3, Vector2, Vector3, Vector4};
+use gl;
+
+/// A value that can be uploaded to a uniform of the program currently in use,
+/// see `Shader::set`
+pub trait Uniform {
+    unsafe fn set_uniform(&self, location: i32);
+}
+
+/// A `Uniform` that can also be uploaded as a whole array at once
+/// (`uniform vec3 positions[4]`), which makes slices, `Vec`s and arrays of it
+/// uniforms as well
+pub trait UniformArray: Sized {
+    unsafe fn set_uniform_array(values: &[Self], location: i32);
+}
+
+macro_rules! impl_uniform {
+    ($ty:ty, |$location:ident, $count:ident, $ptr:ident: $elem:ty| $set:expr) => {
+        impl UniformArray for $ty {
+            unsafe fn set_uniform_array(values: &[Self], $location: i32) {
+                let $count = values.len() as i32;
+                let $ptr = values.as_ptr() as *const $elem;
+                $set;
+            }
+        }
+
+        impl Uniform for $ty {
+            unsafe fn set_uniform(&self, location: i32) {
+                <$ty>::set_uniform_array(std::slice::from_ref(self), location);
+            }
+        }
+    };
+}
+
+impl_uniform!(f32, |location, count, ptr: f32| gl::Uniform1fv(location, count, ptr));
+impl_uniform!(i32, |location, count, ptr: i32| gl::Uniform1iv(location, count, ptr));
+impl_uniform!(u32, |location, count, ptr: u32| gl::Uniform1uiv(location, count, ptr));
+impl_uniform!(Vector2<f32>, |location, count, ptr: f32| gl::Uniform2fv(location, count, ptr));
+impl_uniform!(Vector3<f32>, |location, count, ptr: f32| gl::Uniform3fv(location, count, ptr));
+impl_uniform!(Vector4<f32>, |location, count, ptr: f32| gl::Uniform4fv(location, count, ptr));
+impl_uniform!(Point3<f32>, |location, count, ptr: f32| gl::Uniform3fv(location, count, ptr));
+impl_uniform!(Matrix3<f32>, |location, count, ptr: f32| gl::UniformMatrix3fv(location, count, gl::FALSE, ptr));
+impl_uniform!(Matrix4<f32>, |location, count, ptr: f32| gl::UniformMatrix4fv(location, count, gl::FALSE, ptr));
+
+// GLSL bools are set as ints, so these need a conversion first
+impl UniformArray for bool {
+    unsafe fn set_uniform_array(values: &[Self], location: i32) {
+        let values: Vec<i32> = values.iter().map(|b| *b as i32).collect();
+        i32::set_uniform_array(&values, location);
+    }
+}
+
+impl Uniform for bool {
+    unsafe fn set_uniform(&self, location: i32) {
+        (*self as i32).set_uniform(location);
+    }
+}
+
+impl<T: UniformArray> Uniform for [T] {
+    unsafe fn set_uniform(&self, location: i32) {
+        T::set_uniform_array(self, location);
+    }
+}
+
+impl<T: UniformArray> Uniform for Vec<T> {
+    unsafe fn set_uniform(&self, location: i32) {
+        T::set_uniform_array(self, location);
+    }
+}
+
+macro_rules! impl_uniform_for_arrays {
+    ($($n:expr),*) => {
+        $(
+            impl<T: UniformArray> Uniform for [T; $n] {
+                unsafe fn set_uniform(&self, location: i32) {
+                    T::set_uniform_array(self, location);
+                }
+            }
+        )*
+    };
+}
+
+impl_uniform_for_arrays!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 32, 64);
@@ -0,0 +1,103 @@
+#![allow(dead_code)]
+
+use std::marker::PhantomData;
+use std::os::raw::c_void;
+use std::ptr;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use gl;
+
+use crate::shared::{align_to, Shader, Std140};
+
+/// Next free uniform buffer binding point, see `UniformBuffer::new`
+static NEXT_BINDING: AtomicU32 = AtomicU32::new(0);
+
+/// A uniform buffer object holding one `T` in std140 layout, bound to its own
+/// binding point. Shaders get access to it with `Shader::bind_uniform_block`.
+pub struct UniformBuffer<T: Std140> {
+    pub id: u32,
+    binding: u32,
+    _data: PhantomData<T>,
+}
+
+impl<T: Std140> UniformBuffer<T> {
+    /// Creates the buffer (with undefined contents) and binds it to the next
+    /// unused binding point. Binding points aren't reused, which is plenty for
+    /// the tutorials; use `with_binding` to pick one yourself.
+    pub unsafe fn new() -> UniformBuffer<T> {
+        let binding = NEXT_BINDING.fetch_add(1, Ordering::SeqCst);
+        UniformBuffer::with_binding(binding)
+    }
+
+    pub unsafe fn with_binding(binding: u32) -> UniformBuffer<T> {
+        let mut max_bindings = 0;
+        gl::GetIntegerv(gl::MAX_UNIFORM_BUFFER_BINDINGS, &mut max_bindings);
+        assert!(
+            (binding as i32) < max_bindings,
+            "uniform buffer binding point {} exceeds GL_MAX_UNIFORM_BUFFER_BINDINGS ({})",
+            binding,
+            max_bindings
+        );
+
+        let mut id = 0;
+        gl::GenBuffers(1, &mut id);
+        gl::BindBuffer(gl::UNIFORM_BUFFER, id);
+        gl::BufferData(gl::UNIFORM_BUFFER, T::SIZE as isize, ptr::null(), gl::DYNAMIC_DRAW);
+        gl::BindBuffer(gl::UNIFORM_BUFFER, 0);
+        // link the whole buffer to the binding point
+        gl::BindBufferRange(gl::UNIFORM_BUFFER, binding, id, 0, T::SIZE as isize);
+
+        UniformBuffer { id, binding, _data: PhantomData }
+    }
+
+    pub fn binding(&self) -> u32 {
+        self.binding
+    }
+
+    /// Uploads the whole block
+    pub unsafe fn set(&self, value: &T) {
+        self.set_range(0, value);
+    }
+
+    /// Uploads a single member of the block at byte `offset`, e.g. only the
+    /// view matrix which changes every frame
+    pub unsafe fn set_range<M: Std140>(&self, offset: usize, value: &M) {
+        assert!(offset + M::SIZE <= T::SIZE, "write at {}..{} outside of the uniform block", offset, offset + M::SIZE);
+        let bytes = value.to_std140_bytes();
+        gl::BindBuffer(gl::UNIFORM_BUFFER, self.id);
+        gl::BufferSubData(gl::UNIFORM_BUFFER, offset as isize, M::SIZE as isize, bytes.as_ptr() as *const c_void);
+        gl::BindBuffer(gl::UNIFORM_BUFFER, 0);
+    }
+}
+
+impl<T: Std140> Drop for UniformBuffer<T> {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteBuffers(1, &self.id);
+        }
+    }
+}
+
+impl Shader {
+    /// Connects the uniform block `name` of this program to `buffer`'s binding
+    /// point. Warns if the program has no such block or its size doesn't match.
+    pub unsafe fn bind_uniform_block<T: Std140>(&self, name: &str, buffer: &UniformBuffer<T>) {
+        let block = self.uniform_blocks().into_iter().find(|block| block.name == name);
+        match block {
+            Some(block) => {
+                // drivers may report the size including trailing padding
+                if align_to(block.data_size as usize, 16) != align_to(T::SIZE, 16) {
+                    println!(
+                        "WARNING::SHADER: uniform block '{}' of program {} is {} bytes, but the buffer holds {}",
+                        name,
+                        self.id,
+                        block.data_size,
+                        T::SIZE
+                    );
+                }
+                gl::UniformBlockBinding(self.id, block.index, buffer.binding());
+            }
+            None => println!("WARNING::SHADER: program {} has no active uniform block '{}'", self.id, name),
+        }
+    }
+}
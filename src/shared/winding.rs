@@ -0,0 +1,166 @@
+#![allow(dead_code)]
+
+use cgmath::prelude::*;
+use cgmath::{vec2, vec3, Vector3};
+
+use crate::shared::Vertex;
+
+/// The textured cube of the lighting chapters, 36 vertices of position,
+/// normal and texture coordinates. Kept with the winding it was copied
+/// around with: half of its faces are clockwise when looked at from outside,
+/// so run it through `fix_winding` before enabling face culling.
+#[rustfmt::skip]
+pub const CUBE_VERTICES: [f32; 288] = [
+    // positions       // normals        // texture coords
+    -0.5, -0.5, -0.5,  0.0,  0.0, -1.0,  0.0, 0.0,
+     0.5, -0.5, -0.5,  0.0,  0.0, -1.0,  1.0, 0.0,
+     0.5,  0.5, -0.5,  0.0,  0.0, -1.0,  1.0, 1.0,
+     0.5,  0.5, -0.5,  0.0,  0.0, -1.0,  1.0, 1.0,
+    -0.5,  0.5, -0.5,  0.0,  0.0, -1.0,  0.0, 1.0,
+    -0.5, -0.5, -0.5,  0.0,  0.0, -1.0,  0.0, 0.0,
+
+    -0.5, -0.5,  0.5,  0.0,  0.0,  1.0,  0.0, 0.0,
+     0.5, -0.5,  0.5,  0.0,  0.0,  1.0,  1.0, 0.0,
+     0.5,  0.5,  0.5,  0.0,  0.0,  1.0,  1.0, 1.0,
+     0.5,  0.5,  0.5,  0.0,  0.0,  1.0,  1.0, 1.0,
+    -0.5,  0.5,  0.5,  0.0,  0.0,  1.0,  0.0, 1.0,
+    -0.5, -0.5,  0.5,  0.0,  0.0,  1.0,  0.0, 0.0,
+
+    -0.5,  0.5,  0.5, -1.0,  0.0,  0.0,  1.0, 0.0,
+    -0.5,  0.5, -0.5, -1.0,  0.0,  0.0,  1.0, 1.0,
+    -0.5, -0.5, -0.5, -1.0,  0.0,  0.0,  0.0, 1.0,
+    -0.5, -0.5, -0.5, -1.0,  0.0,  0.0,  0.0, 1.0,
+    -0.5, -0.5,  0.5, -1.0,  0.0,  0.0,  0.0, 0.0,
+    -0.5,  0.5,  0.5, -1.0,  0.0,  0.0,  1.0, 0.0,
+
+     0.5,  0.5,  0.5,  1.0,  0.0,  0.0,  1.0, 0.0,
+     0.5,  0.5, -0.5,  1.0,  0.0,  0.0,  1.0, 1.0,
+     0.5, -0.5, -0.5,  1.0,  0.0,  0.0,  0.0, 1.0,
+     0.5, -0.5, -0.5,  1.0,  0.0,  0.0,  0.0, 1.0,
+     0.5, -0.5,  0.5,  1.0,  0.0,  0.0,  0.0, 0.0,
+     0.5,  0.5,  0.5,  1.0,  0.0,  0.0,  1.0, 0.0,
+
+    -0.5, -0.5, -0.5,  0.0, -1.0,  0.0,  0.0, 1.0,
+     0.5, -0.5, -0.5,  0.0, -1.0,  0.0,  1.0, 1.0,
+     0.5, -0.5,  0.5,  0.0, -1.0,  0.0,  1.0, 0.0,
+     0.5, -0.5,  0.5,  0.0, -1.0,  0.0,  1.0, 0.0,
+    -0.5, -0.5,  0.5,  0.0, -1.0,  0.0,  0.0, 0.0,
+    -0.5, -0.5, -0.5,  0.0, -1.0,  0.0,  0.0, 1.0,
+
+    -0.5,  0.5, -0.5,  0.0,  1.0,  0.0,  0.0, 1.0,
+     0.5,  0.5, -0.5,  0.0,  1.0,  0.0,  1.0, 1.0,
+     0.5,  0.5,  0.5,  0.0,  1.0,  0.0,  1.0, 0.0,
+     0.5,  0.5,  0.5,  0.0,  1.0,  0.0,  1.0, 0.0,
+    -0.5,  0.5,  0.5,  0.0,  1.0,  0.0,  0.0, 0.0,
+    -0.5,  0.5, -0.5,  0.0,  1.0,  0.0,  0.0, 1.0,
+];
+
+/// Turns interleaved position, normal and texture coordinate floats, like
+/// `CUBE_VERTICES`, into vertices for a `Mesh`
+pub fn interleaved_vertices(data: &[f32]) -> Vec<Vertex> {
+    assert_eq!(data.len() % 8, 0, "ERROR::WINDING: expected 8 floats per vertex, got {} floats", data.len());
+    data.chunks(8)
+        .map(|v| Vertex {
+            position: vec3(v[0], v[1], v[2]),
+            normal: vec3(v[3], v[4], v[5]),
+            tex_coords: vec2(v[6], v[7]),
+            ..Vertex::default()
+        })
+        .collect()
+}
+
+/// `0..count`, the indices of an unindexed triangle list
+pub fn sequential_indices(count: usize) -> Vec<u32> {
+    (0..count as u32).collect()
+}
+
+/// The normal of the side of triangle `a b c` from which its corners appear
+/// counter-clockwise, i.e. the side OpenGL treats as front facing by default.
+/// Not normalized, zero for degenerate triangles.
+pub fn front_face_normal(a: Vector3<f32>, b: Vector3<f32>, c: Vector3<f32>) -> Vector3<f32> {
+    (b - a).cross(c - a)
+}
+
+/// Whether the front face of the triangle made of `corners` points away from
+/// the normals stored in its vertices, i.e. it's wound clockwise when looked
+/// at from the side the normals point to and would be culled from there.
+/// Degenerate triangles and triangles without normals are never misoriented.
+fn is_misoriented(vertices: &[Vertex], corners: [usize; 3]) -> bool {
+    let [a, b, c] = corners;
+    let (a, b, c) = (&vertices[a], &vertices[b], &vertices[c]);
+    let outward = a.normal + b.normal + c.normal;
+    front_face_normal(a.position, b.position, c.position).dot(outward) < 0.0
+}
+
+fn corners(indices: &[u32], triangle: usize) -> [usize; 3] {
+    let first = triangle * 3;
+    [indices[first] as usize, indices[first + 1] as usize, indices[first + 2] as usize]
+}
+
+/// Triangles (by their position in `indices`, divided by 3) whose winding
+/// disagrees with the vertex normals; see `fix_winding`
+pub fn check_winding(vertices: &[Vertex], indices: &[u32]) -> Vec<usize> {
+    assert_eq!(indices.len() % 3, 0, "ERROR::WINDING: {} indices don't make up whole triangles", indices.len());
+    (0..indices.len() / 3).filter(|&triangle| is_misoriented(vertices, corners(indices, triangle))).collect()
+}
+
+/// Makes every triangle counter-clockwise when looked at from the side its
+/// vertex normals point to, by swapping two of its indices. Returns the
+/// number of triangles that were flipped.
+pub fn fix_winding(vertices: &[Vertex], indices: &mut [u32]) -> usize {
+    let misoriented = check_winding(vertices, indices);
+    for triangle in &misoriented {
+        indices.swap(triangle * 3 + 1, triangle * 3 + 2);
+    }
+    misoriented.len()
+}
+
+/// `check_winding` for an unindexed triangle list
+pub fn check_winding_unindexed(vertices: &[Vertex]) -> Vec<usize> {
+    check_winding(vertices, &sequential_indices(vertices.len()))
+}
+
+/// `fix_winding` for an unindexed triangle list, swaps vertices instead of indices
+pub fn fix_winding_unindexed(vertices: &mut [Vertex]) -> usize {
+    let misoriented = check_winding_unindexed(vertices);
+    for triangle in &misoriented {
+        vertices.swap(triangle * 3 + 1, triangle * 3 + 2);
+    }
+    misoriented.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the -Z, +X and +Y faces of `CUBE_VERTICES` are clockwise from outside
+    const MISORIENTED: [usize; 6] = [0, 1, 6, 7, 10, 11];
+
+    #[test]
+    fn check_winding_finds_the_clockwise_cube_faces() {
+        let vertices = interleaved_vertices(&CUBE_VERTICES);
+        assert_eq!(check_winding_unindexed(&vertices), MISORIENTED);
+    }
+
+    #[test]
+    fn fix_winding_unindexed_fixes_the_cube() {
+        let mut vertices = interleaved_vertices(&CUBE_VERTICES);
+        assert_eq!(fix_winding_unindexed(&mut vertices), MISORIENTED.len());
+        assert!(check_winding_unindexed(&vertices).is_empty());
+    }
+
+    #[test]
+    fn fix_winding_is_idempotent() {
+        let vertices = interleaved_vertices(&CUBE_VERTICES);
+        let mut indices = sequential_indices(vertices.len());
+        assert_eq!(fix_winding(&vertices, &mut indices), MISORIENTED.len());
+        assert!(check_winding(&vertices, &indices).is_empty());
+        assert_eq!(fix_winding(&vertices, &mut indices), 0);
+    }
+
+    #[test]
+    fn counter_clockwise_triangle_faces_the_viewer() {
+        let normal = front_face_normal(vec3(0.0, 0.0, 0.0), vec3(1.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0));
+        assert_eq!(normal.normalize(), vec3(0.0, 0.0, 1.0));
+    }
+}
@@ -1,19 +1,36 @@
-#[cfg(feature = "chapter-1")]
-pub use _1_getting_started::*;
-#[cfg(feature = "chapter-2")]
-pub use _2_lighting::*;
-#[cfg(feature = "chapter-3")]
-pub use _3_model_loading::*;
-#[cfg(feature = "chapter-4")]
-pub use _4_advanced_opengl::*;
 use internal::*;
+pub use registry::*;
 
-#[cfg(feature = "chapter-1")]
-pub mod _1_getting_started;
-#[cfg(feature = "chapter-2")]
-pub mod _2_lighting;
-#[cfg(feature = "chapter-3")]
-pub mod _3_model_loading;
-#[cfg(feature = "chapter-4")]
-pub mod _4_advanced_opengl;
 mod internal;
+mod registry;
+
+/// Declares every chapter module behind its cargo feature, re-exports it and
+/// lists its `TUTORIALS` for the registry, so a chapter is gated in one place
+macro_rules! chapters {
+    ($($feature:literal => $chapter:ident),* $(,)?) => {
+        $(
+            // only some chapters have items the others use
+            #[cfg(feature = $feature)]
+            #[allow(unused_imports)]
+            pub use $chapter::*;
+            #[cfg(feature = $feature)]
+            pub mod $chapter;
+        )*
+
+        /// The `TUTORIALS` of every enabled chapter, in reading order
+        fn chapter_tables() -> Vec<&'static [Tutorial]> {
+            vec![$(
+                #[cfg(feature = $feature)]
+                $chapter::TUTORIALS,
+            )*]
+        }
+    };
+}
+
+chapters! {
+    "chapter-1" => _1_getting_started,
+    "chapter-2" => _2_lighting,
+    "chapter-3" => _3_model_loading,
+    "chapter-4" => _4_advanced_opengl,
+    "chapter-5" => _5_advanced_lighting,
+}
@@ -1,3 +1,5 @@
+use crate::tutorial::Tutorial;
+
 pub use _1_1_hello_window::*;
 pub use _1_2_hello_window_clear::*;
 pub use _2_1_hello_triangle::*;
@@ -58,3 +60,27 @@ pub mod _7_2_camera_keyboard_dt;
 pub mod _7_3_camera_mouse_zoom;
 
 pub mod _7_4_camera_class;
+
+/// tutorials of this chapter in reading order
+pub const TUTORIALS: &[Tutorial] = &[
+    Tutorial { id: "1_1_1", chapter: 1, title: "Hello Window", main: main_1_1_1 },
+    Tutorial { id: "1_1_2", chapter: 1, title: "Hello Window Clear", main: main_1_1_2 },
+    Tutorial { id: "1_2_1", chapter: 1, title: "Hello Triangle", main: main_1_2_1 },
+    Tutorial { id: "1_2_2", chapter: 1, title: "Hello Triangle Indexed", main: main_1_2_2 },
+    Tutorial { id: "1_2_3", chapter: 1, title: "Hello Triangle Exercise 1", main: main_1_2_3 },
+    Tutorial { id: "1_2_4", chapter: 1, title: "Hello Triangle Exercise 2", main: main_1_2_4 },
+    Tutorial { id: "1_2_5", chapter: 1, title: "Hello Triangle Exercise 3", main: main_1_2_5 },
+    Tutorial { id: "1_3_1", chapter: 1, title: "Shaders Uniform", main: main_1_3_1 },
+    Tutorial { id: "1_3_2", chapter: 1, title: "Shaders Interpolation", main: main_1_3_2 },
+    Tutorial { id: "1_3_3", chapter: 1, title: "Shaders Class", main: main_1_3_3 },
+    Tutorial { id: "1_4_1", chapter: 1, title: "Textures", main: main_1_4_1 },
+    Tutorial { id: "1_4_2", chapter: 1, title: "Textures Combined", main: main_1_4_2 },
+    Tutorial { id: "1_5_1", chapter: 1, title: "Transformations", main: main_1_5_1 },
+    Tutorial { id: "1_6_1", chapter: 1, title: "Coordinate Systems", main: main_1_6_1 },
+    Tutorial { id: "1_6_2", chapter: 1, title: "Coordinate Systems Depth", main: main_1_6_2 },
+    Tutorial { id: "1_6_3", chapter: 1, title: "Coordinate Systems Multiple", main: main_1_6_3 },
+    Tutorial { id: "1_7_1", chapter: 1, title: "Camera Circle", main: main_1_7_1 },
+    Tutorial { id: "1_7_2", chapter: 1, title: "Camera Keyboard Delta Time", main: main_1_7_2 },
+    Tutorial { id: "1_7_3", chapter: 1, title: "Camera Mouse Zoom", main: main_1_7_3 },
+    Tutorial { id: "1_7_4", chapter: 1, title: "Camera Class", main: main_1_7_4 },
+];
@@ -1,5 +1,6 @@
 use glfw::Context;
 
+use crate::shared::{create_window, window_should_close};
 use crate::tutorial::process_events;
 
 // settings
@@ -17,9 +18,7 @@ pub fn main_1_1_2() {
 
     // glfw window creation
     // --------------------
-    let (mut window, events) = glfw
-        .create_window(SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL", glfw::WindowMode::Windowed)
-        .expect("Failed to create GLFW window");
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
 
     window.make_current();
     window.set_key_polling(true);
@@ -31,7 +30,7 @@ pub fn main_1_1_2() {
 
     // render loop
     // -----------
-    while !window.should_close() {
+    while !window_should_close(&mut glfw, &window) {
         // events
         // -----
         process_events(&mut window, &events);
@@ -9,7 +9,7 @@ use std::str;
 use gl::types::*;
 use glfw::Context;
 
-use crate::shared::check_compile_errors;
+use crate::shared::{check_compile_errors, create_window, window_should_close};
 use crate::tutorial::process_events;
 
 // settings
@@ -43,9 +43,7 @@ pub fn main_1_2_3() {
 
     // glfw window creation
     // --------------------
-    let (mut window, events) = glfw
-        .create_window(SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL", glfw::WindowMode::Windowed)
-        .expect("Failed to create GLFW window");
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
 
     window.make_current();
     window.set_key_polling(true);
@@ -125,7 +123,7 @@ pub fn main_1_2_3() {
 
     // render loop
     // -----------
-    while !window.should_close() {
+    while !window_should_close(&mut glfw, &window) {
         // events
         // -----
         process_events(&mut window, &events);
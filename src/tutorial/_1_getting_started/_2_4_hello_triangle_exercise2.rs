@@ -4,6 +4,7 @@ use std::str;
 
 use glfw::Context;
 
+use crate::shared::{create_window, window_should_close};
 use crate::tutorial::{process_events, TutorialGeometry, TutorialShader};
 
 // settings
@@ -37,9 +38,7 @@ pub fn main_1_2_4() {
 
     // glfw window creation
     // --------------------
-    let (mut window, events) = glfw
-        .create_window(SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL", glfw::WindowMode::Windowed)
-        .expect("Failed to create GLFW window");
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
 
     window.make_current();
     window.set_key_polling(true);
@@ -60,7 +59,7 @@ pub fn main_1_2_4() {
 
     // render loop
     // -----------
-    while !window.should_close() {
+    while !window_should_close(&mut glfw, &window) {
         // events
         // -----
         process_events(&mut window, &events);
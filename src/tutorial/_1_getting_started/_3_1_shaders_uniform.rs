@@ -4,6 +4,7 @@ use std::str;
 
 use glfw::Context;
 
+use crate::shared::{create_window, get_time, window_should_close};
 use crate::tutorial::{process_events, TutorialGeometry, TutorialShader};
 
 // settings
@@ -38,9 +39,7 @@ pub fn main_1_3_1() {
 
     // glfw window creation
     // --------------------
-    let (mut window, events) = glfw
-        .create_window(SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL", glfw::WindowMode::Windowed)
-        .expect("Failed to create GLFW window");
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
 
     window.make_current();
     window.set_key_polling(true);
@@ -64,7 +63,7 @@ pub fn main_1_3_1() {
 
     // render loop
     // -----------
-    while !window.should_close() {
+    while !window_should_close(&mut glfw, &window) {
         // events
         // -----
         process_events(&mut window, &events);
@@ -75,7 +74,7 @@ pub fn main_1_3_1() {
             gl::ClearColor(0.2, 0.3, 0.3, 1.0);
             gl::Clear(gl::COLOR_BUFFER_BIT);
 
-            let time_value = glfw.get_time() as f32;
+            let time_value = get_time(&glfw) as f32;
             let green = time_value.sin() / 2.0 + 0.5;
             shader.uniform4f(location, 0.0, green, 0.0, 1.0);
             triangle.draw();
@@ -6,7 +6,7 @@ use std::path::Path;
 use glfw::Context;
 use image::GenericImageView;
 
-use crate::shared::Shader;
+use crate::shared::{create_window, window_should_close, Shader};
 use crate::tutorial::{process_events, TutorialGeometry};
 
 // settings
@@ -24,9 +24,7 @@ pub fn main_1_4_1() {
 
     // glfw window creation
     // --------------------
-    let (mut window, events) = glfw
-        .create_window(SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL", glfw::WindowMode::Windowed)
-        .expect("Failed to create GLFW window");
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
 
     window.make_current();
     window.set_key_polling(true);
@@ -89,7 +87,7 @@ pub fn main_1_4_1() {
 
     // render loop
     // -----------
-    while !window.should_close() {
+    while !window_should_close(&mut glfw, &window) {
         // events
         // -----
         process_events(&mut window, &events);
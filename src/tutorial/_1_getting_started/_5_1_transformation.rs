@@ -6,7 +6,7 @@ use cgmath::{vec3, Matrix, Matrix4, Rad, SquareMatrix};
 use glfw::Context;
 
 use crate::c_str;
-use crate::shared::Shader;
+use crate::shared::{create_window, get_time, window_should_close, Shader};
 use crate::tutorial::{process_events, TutorialGeometry, TutorialTexture};
 
 // settings
@@ -24,9 +24,7 @@ pub fn main_1_5_1() {
 
     // glfw window creation
     // --------------------
-    let (mut window, events) = glfw
-        .create_window(SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL", glfw::WindowMode::Windowed)
-        .expect("Failed to create GLFW window");
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
 
     window.make_current();
     window.set_key_polling(true);
@@ -72,7 +70,7 @@ pub fn main_1_5_1() {
 
     // render loop
     // -----------
-    while !window.should_close() {
+    while !window_should_close(&mut glfw, &window) {
         // events
         // -----
         process_events(&mut window, &events);
@@ -88,7 +86,7 @@ pub fn main_1_5_1() {
 
             let transform = Matrix4::<f32>::identity()
                 * Matrix4::<f32>::from_translation(vec3(0.5, -0.5, 0.0))
-                * Matrix4::<f32>::from_angle_z(Rad(glfw.get_time() as f32));
+                * Matrix4::<f32>::from_angle_z(Rad(get_time(&glfw) as f32));
 
             shader.use_program();
             gl::UniformMatrix4fv(transform_location, 1, gl::FALSE, transform.as_ptr());
@@ -6,7 +6,7 @@ use cgmath::{perspective, vec3, Deg, InnerSpace, Matrix, Matrix4, Rad, Vector3};
 use glfw::Context;
 
 use crate::c_str;
-use crate::shared::Shader;
+use crate::shared::{create_window, get_time, window_should_close, Shader};
 use crate::tutorial::{process_events, TutorialGeometry, TutorialTexture};
 
 // settings
@@ -24,9 +24,7 @@ pub fn main_1_6_3() {
 
     // glfw window creation
     // --------------------
-    let (mut window, events) = glfw
-        .create_window(SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL", glfw::WindowMode::Windowed)
-        .expect("Failed to create GLFW window");
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
 
     window.make_current();
     window.set_key_polling(true);
@@ -94,7 +92,7 @@ pub fn main_1_6_3() {
 
     // render loop
     // -----------
-    while !window.should_close() {
+    while !window_should_close(&mut glfw, &window) {
         // events
         // -----
         process_events(&mut window, &events);
@@ -109,7 +107,7 @@ pub fn main_1_6_3() {
             texture2.bind();
 
             let model: Matrix4<f32> =
-                Matrix4::from_axis_angle(vec3(0.5, 1.0, 0.0).normalize(), Rad(glfw.get_time() as f32));
+                Matrix4::from_axis_angle(vec3(0.5, 1.0, 0.0).normalize(), Rad(get_time(&glfw) as f32));
             let view: Matrix4<f32> = Matrix4::from_translation(vec3(0., 0., -3.));
 
             shader.use_program();
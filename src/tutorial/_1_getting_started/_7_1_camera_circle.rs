@@ -6,7 +6,7 @@ use cgmath::{perspective, vec3, Deg, InnerSpace, Matrix, Matrix4, Point3, Vector
 use glfw::Context;
 
 use crate::c_str;
-use crate::shared::Shader;
+use crate::shared::{create_window, get_time, window_should_close, Shader};
 use crate::tutorial::{process_events, TutorialGeometry, TutorialTexture};
 
 // settings
@@ -24,9 +24,7 @@ pub fn main_1_7_1() {
 
     // glfw window creation
     // --------------------
-    let (mut window, events) = glfw
-        .create_window(SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL", glfw::WindowMode::Windowed)
-        .expect("Failed to create GLFW window");
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
 
     window.make_current();
     window.set_key_polling(true);
@@ -93,7 +91,7 @@ pub fn main_1_7_1() {
 
     // render loop
     // -----------
-    while !window.should_close() {
+    while !window_should_close(&mut glfw, &window) {
         // events
         // -----
         process_events(&mut window, &events);
@@ -111,8 +109,8 @@ pub fn main_1_7_1() {
 
             // camera/view transformation
             let radius: f32 = 10.0;
-            let cam_x = glfw.get_time().sin() as f32 * radius;
-            let cam_z = glfw.get_time().cos() as f32 * radius;
+            let cam_x = get_time(&glfw).sin() as f32 * radius;
+            let cam_z = get_time(&glfw).cos() as f32 * radius;
             let view: Matrix4<f32> =
                 Matrix4::look_at(Point3::new(cam_x, 0.0, cam_z), Point3::new(0.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0));
             gl::UniformMatrix4fv(loc_view, 1, gl::FALSE, &view[0][0]);
@@ -7,7 +7,7 @@ use cgmath::{perspective, vec3, Deg, InnerSpace, Matrix, Matrix4, Point3, Vector
 use glfw::{Action, Context, Key};
 
 use crate::c_str;
-use crate::shared::Shader;
+use crate::shared::{create_window, get_time, window_should_close, Shader};
 use crate::tutorial::{TutorialGeometry, TutorialTexture};
 
 // settings
@@ -40,9 +40,7 @@ pub fn main_1_7_3() {
 
     // glfw window creation
     // --------------------
-    let (mut window, events) = glfw
-        .create_window(SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL", glfw::WindowMode::Windowed)
-        .expect("Failed to create GLFW window");
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
 
     window.make_current();
     window.set_key_polling(true);
@@ -112,10 +110,10 @@ pub fn main_1_7_3() {
 
     // render loop
     // -----------
-    while !window.should_close() {
+    while !window_should_close(&mut glfw, &window) {
         // pre-frame time logic
         // --------------------
-        let current_frame = glfw.get_time() as f32;
+        let current_frame = get_time(&glfw) as f32;
         delta_time = current_frame - last_frame;
         last_frame = current_frame;
 
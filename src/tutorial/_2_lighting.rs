@@ -1,3 +1,5 @@
+use crate::tutorial::Tutorial;
+
 pub use _1_colors::*;
 pub use _2_1_basic_lighting_diffuse::*;
 pub use _2_2_basic_lighting_specular::*;
@@ -11,13 +13,38 @@ pub use _5_4_light_casters_spot_soft::*;
 pub use _6_multiple_lights::*;
 
 pub mod _1_colors;
+
 pub mod _2_1_basic_lighting_diffuse;
+
 pub mod _2_2_basic_lighting_specular;
+
 pub mod _3_1_materials;
+
 pub mod _4_1_lighting_maps_diffuse_map;
+
 pub mod _4_2_lighting_maps_specular_map;
+
 pub mod _5_1_lighting_casters_directional;
+
 pub mod _5_2_lighting_casters_point;
+
 pub mod _5_3_light_casters_spot;
+
 pub mod _5_4_light_casters_spot_soft;
+
 pub mod _6_multiple_lights;
+
+/// tutorials of this chapter in reading order
+pub const TUTORIALS: &[Tutorial] = &[
+    Tutorial { id: "2_1", chapter: 2, title: "Colors", main: main_2_1 },
+    Tutorial { id: "2_2_1", chapter: 2, title: "Basic Lighting Diffuse", main: main_2_2_1 },
+    Tutorial { id: "2_2_2", chapter: 2, title: "Basic Lighting Specular", main: main_2_2_2 },
+    Tutorial { id: "2_3_1", chapter: 2, title: "Materials", main: main_2_3_1 },
+    Tutorial { id: "2_4_1", chapter: 2, title: "Lighting Maps Diffuse Map", main: main_2_4_1 },
+    Tutorial { id: "2_4_2", chapter: 2, title: "Lighting Maps Specular Map", main: main_2_4_2 },
+    Tutorial { id: "2_5_1", chapter: 2, title: "Light Casters Directional", main: main_2_5_1 },
+    Tutorial { id: "2_5_2", chapter: 2, title: "Light Casters Point", main: main_2_5_2 },
+    Tutorial { id: "2_5_3", chapter: 2, title: "Light Casters Spot", main: main_2_5_3 },
+    Tutorial { id: "2_5_4", chapter: 2, title: "Light Casters Spot Soft", main: main_2_5_4 },
+    Tutorial { id: "2_6", chapter: 2, title: "Multiple Lights", main: main_2_6 },
+];
@@ -10,7 +10,7 @@ use gl::types::*;
 use glfw::Context;
 
 use crate::c_str;
-use crate::shared::{process_events, process_input, Camera, Shader};
+use crate::shared::{create_window, get_time, process_events, process_input, window_should_close, Camera, Shader};
 
 // settings
 const SCR_WIDTH: u32 = 800;
@@ -39,9 +39,7 @@ pub fn main_2_2_2() {
 
     // glfw window creation
     // --------------------
-    let (mut window, events) = glfw
-        .create_window(SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL", glfw::WindowMode::Windowed)
-        .expect("Failed to create GLFW window");
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
 
     window.make_current();
     window.set_key_polling(true);
@@ -124,10 +122,10 @@ pub fn main_2_2_2() {
 
     // render loop
     // -----------
-    while !window.should_close() {
+    while !window_should_close(&mut glfw, &window) {
         // pre-frame time logic
         // --------------------
-        let current_frame = glfw.get_time() as f32;
+        let current_frame = get_time(&glfw) as f32;
         delta_time = current_frame - last_frame;
         last_frame = current_frame;
 
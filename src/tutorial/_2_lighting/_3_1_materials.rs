@@ -10,7 +10,7 @@ use gl::types::*;
 use glfw::Context;
 
 use crate::c_str;
-use crate::shared::{process_events, process_input, Camera, Shader};
+use crate::shared::{create_window, get_time, process_events, process_input, window_should_close, Camera, Shader};
 
 // settings
 const SCR_WIDTH: u32 = 800;
@@ -39,9 +39,7 @@ pub fn main_2_3_1() {
 
     // glfw window creation
     // --------------------
-    let (mut window, events) = glfw
-        .create_window(SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL", glfw::WindowMode::Windowed)
-        .expect("Failed to create GLFW window");
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
 
     window.make_current();
     window.set_key_polling(true);
@@ -124,10 +122,10 @@ pub fn main_2_3_1() {
 
     // render loop
     // -----------
-    while !window.should_close() {
+    while !window_should_close(&mut glfw, &window) {
         // pre-frame time logic
         // --------------------
-        let current_frame = glfw.get_time() as f32;
+        let current_frame = get_time(&glfw) as f32;
         delta_time = current_frame - last_frame;
         last_frame = current_frame;
 
@@ -151,7 +149,7 @@ pub fn main_2_3_1() {
             light_shader.set_vector3(c_str!("viewPos"), &camera.position.to_vec());
 
             // light properties
-            let time = glfw.get_time() as f32;
+            let time = get_time(&glfw) as f32;
             let light_color = Vector3 { x: (time * 2.0).sin(), y: (time * 0.7).sin(), z: (time * 1.3).sin() };
             let diffuse_color = light_color * 0.5;
             let ambient_color = diffuse_color * 0.2;
@@ -10,12 +10,17 @@ use gl::types::*;
 use glfw::Context;
 
 use crate::c_str;
-use crate::shared::{load_texture, process_events, process_input, Camera, Shader};
+use crate::shared::{
+    create_window, get_time, load_texture, process_events, process_input, window_should_close, Camera, ReloadableShader,
+};
 
 // settings
 const SCR_WIDTH: u32 = 480;
 const SCR_HEIGHT: u32 = 320;
 
+/// passed on to the fragment shader as a #define
+const NR_POINT_LIGHTS: usize = 4;
+
 pub fn main_2_6() {
     let mut camera = Camera { position: Point3::new(0.0, 0.0, 3.0), ..Camera::default() };
 
@@ -36,9 +41,7 @@ pub fn main_2_6() {
 
     // glfw window creation
     // --------------------
-    let (mut window, events) = glfw
-        .create_window(SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL", glfw::WindowMode::Windowed)
-        .expect("Failed to create GLFW window");
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
 
     window.make_current();
     window.set_key_polling(true);
@@ -53,18 +56,32 @@ pub fn main_2_6() {
     // ---------------------------------------
     gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
 
-    let (light_shader, lamp_shader, vbo, cube_vao, light_vao, diffuse_map, specular_map, cube_pos, point_light_pos) = unsafe {
+    let (
+        mut light_shader,
+        mut lamp_shader,
+        vbo,
+        cube_vao,
+        light_vao,
+        diffuse_map,
+        specular_map,
+        cube_pos,
+        point_light_pos,
+    ) = unsafe {
         // configure global opengl state
         gl::Enable(gl::DEPTH_TEST);
 
         // build and compile our shader program
         // ------------------------------------
-        let light_shader = Shader::new(
+        // edit the shader files while the tutorial is running to see the changes, F5 forces a reload
+        let light_shader = ReloadableShader::with_defines(
             "src/tutorial/_2_lighting/shaders/6.multiple_lights.vsh",
             "src/tutorial/_2_lighting/shaders/6.multiple_lights.fsh",
+            &[("NR_POINT_LIGHTS", &NR_POINT_LIGHTS.to_string())],
+        );
+        let lamp_shader = ReloadableShader::new(
+            "src/tutorial/_2_lighting/shaders/6.lamp.vsh",
+            "src/tutorial/_2_lighting/shaders/6.lamp.fsh",
         );
-        let lamp_shader =
-            Shader::new("src/tutorial/_2_lighting/shaders/6.lamp.vsh", "src/tutorial/_2_lighting/shaders/6.lamp.fsh");
 
         // setup vertex data
         // -----------------
@@ -103,7 +120,7 @@ pub fn main_2_6() {
             vec3(-1.3, 1.0, -1.5),
         ];
 
-        let point_light_pos: [Vector3<f32>; 4] =
+        let point_light_pos: [Vector3<f32>; NR_POINT_LIGHTS] =
             [vec3(0.7, 0.2, 2.0), vec3(2.3, -3.3, -4.0), vec3(-4.0, 2.0, -12.0), vec3(0.0, 0.0, -3.0)];
 
         let (mut vbo, mut cube_vao) = (0, 0);
@@ -156,10 +173,10 @@ pub fn main_2_6() {
 
     // render loop
     // -----------
-    while !window.should_close() {
+    while !window_should_close(&mut glfw, &window) {
         // pre-frame time logic
         // --------------------
-        let current_frame = glfw.get_time() as f32;
+        let current_frame = get_time(&glfw) as f32;
         delta_time = current_frame - last_frame;
         last_frame = current_frame;
 
@@ -171,6 +188,17 @@ pub fn main_2_6() {
         // -----
         process_input(&mut window, delta_time, &mut camera);
 
+        // shader hot-reloading
+        // ---------------------
+        if light_shader.reload_if_changed() {
+            unsafe {
+                light_shader.use_program();
+                light_shader.set_int(c_str!("material.diffuse"), 0);
+                light_shader.set_int(c_str!("material.specular"), 1);
+            }
+        }
+        lamp_shader.reload_if_changed();
+
         // render
         // ------
         unsafe {
@@ -187,14 +215,15 @@ pub fn main_2_6() {
             light_shader.set_vec3(c_str!("dirLight.diffuse"), 0.4, 0.4, 0.4);
             light_shader.set_vec3(c_str!("dirLight.specular"), 0.5, 0.5, 0.5);
             // point lights
-            for i in 0..4 {
-                light_shader.set_vector3(_cstr(&format!("pointLights[{}].position", i)), &point_light_pos[i]);
-                light_shader.set_vec3(_cstr(&format!("pointLights[{}].ambient", i)), 0.05, 0.05, 0.05);
-                light_shader.set_vec3(_cstr(&format!("pointLights[{}].diffuse", i)), 0.8, 0.8, 0.8);
-                light_shader.set_vec3(_cstr(&format!("pointLights[{}].specular", i)), 1.0, 1.0, 1.0);
-                light_shader.set_float(_cstr(&format!("pointLights[{}].constant", i)), 1.0);
-                light_shader.set_float(_cstr(&format!("pointLights[{}].linear", i)), 0.09);
-                light_shader.set_float(_cstr(&format!("pointLights[{}].quadratic", i)), 0.032);
+            for (i, position) in point_light_pos.iter().enumerate() {
+                let point_light = |field: &str| format!("pointLights[{}].{}", i, field);
+                light_shader.set(&point_light("position"), position);
+                light_shader.set(&point_light("ambient"), &vec3(0.05, 0.05, 0.05));
+                light_shader.set(&point_light("diffuse"), &vec3(0.8, 0.8, 0.8));
+                light_shader.set(&point_light("specular"), &vec3(1.0, 1.0, 1.0));
+                light_shader.set(&point_light("constant"), &1.0);
+                light_shader.set(&point_light("linear"), &0.09);
+                light_shader.set(&point_light("quadratic"), &0.032);
             }
             // spot light
             light_shader.set_vector3(c_str!("spotLight.position"), &camera.position.to_vec());
@@ -264,7 +293,3 @@ pub fn main_2_6() {
         gl::DeleteBuffers(1, &vbo);
     }
 }
-
-unsafe fn _cstr(s: &String) -> &CStr {
-    CStr::from_bytes_with_nul_unchecked(s.as_bytes())
-}
@@ -0,0 +1,50 @@
+// Material and light types shared by the light caster and multiple lights
+// shaders. Included with `#include "common/lighting.glsl"`.
+
+struct Material {
+    sampler2D diffuse;
+    sampler2D specular;
+    float shininess;
+};
+
+struct DirLight {
+    vec3 direction;
+
+    vec3 ambient;
+    vec3 diffuse;
+    vec3 specular;
+};
+
+struct PointLight {
+    vec3 position;
+
+    float constant;
+    float linear;
+    float quadratic;
+
+    vec3 ambient;
+    vec3 diffuse;
+    vec3 specular;
+};
+
+struct SpotLight {
+    vec3 position;
+    vec3 direction;
+    float cutOff;
+    float outerCutOff;
+
+    float constant;
+    float linear;
+    float quadratic;
+
+    vec3 ambient;
+    vec3 diffuse;
+    vec3 specular;
+};
+
+// light falloff over distance
+float CalcAttenuation(float constant, float linear, float quadratic, vec3 lightPos, vec3 fragPos)
+{
+    float distance = length(lightPos - fragPos);
+    return 1.0 / (constant + linear * distance + quadratic * (distance * distance));
+}
@@ -1,3 +1,8 @@
-pub mod _1_model_loading;
+use crate::tutorial::Tutorial;
 
 pub use _1_model_loading::*;
+
+pub mod _1_model_loading;
+
+/// tutorials of this chapter in reading order
+pub const TUTORIALS: &[Tutorial] = &[Tutorial { id: "3_1", chapter: 3, title: "Model Loading", main: main_3_1 }];
@@ -1,4 +1,6 @@
-use crate::shared::{process_events, process_input, Camera, Model, Shader};
+use crate::shared::{
+    create_window, get_time, process_events, process_input, window_should_close, Camera, Model, Shader,
+};
 use cgmath::{perspective, vec3, Deg, Matrix4, Point3};
 use glfw::Context;
 use std::ffi::CStr;
@@ -29,9 +31,7 @@ pub fn main_3_1() {
 
     // glfw window creation
     // --------------------
-    let (mut window, events) = glfw
-        .create_window(SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL", glfw::WindowMode::Windowed)
-        .expect("Failed to create GLFW window");
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
 
     window.make_current();
     window.set_framebuffer_size_polling(true);
@@ -67,12 +67,15 @@ pub fn main_3_1() {
         (shader, our_model)
     };
 
+    // print mismatches between the model and the shader after the first frame
+    let mut shader_checked = false;
+
     // render loop
     // -----------
-    while !window.should_close() {
+    while !window_should_close(&mut glfw, &window) {
         // per-frame tie logic
         // -------------------
-        let current_frame = glfw.get_time() as f32;
+        let current_frame = get_time(&glfw) as f32;
         delta_time = current_frame - last_frame;
         last_frame = current_frame;
 
@@ -105,6 +108,13 @@ pub fn main_3_1() {
             model = model * Matrix4::from_scale(0.2);
             shader.set_mat4(c_str!("model"), &model);
             our_model.draw(&shader);
+
+            if !shader_checked {
+                for problem in our_model.check_shader(&shader) {
+                    println!("WARNING::MODEL: {}", problem);
+                }
+                shader_checked = true;
+            }
         }
 
         // glfw:: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
@@ -1,9 +1,82 @@
-pub mod _1_1_depth_testing;
-pub mod _1_2_depth_testing_view;
-pub mod _2_stencil_testing;
-pub mod _3_1_blending_discard;
+use crate::tutorial::Tutorial;
 
+pub use _10_1_instancing_quads::*;
+pub use _10_2_asteroids::*;
+pub use _10_3_asteroids_instanced::*;
+pub use _11_anti_aliasing::*;
 pub use _1_1_depth_testing::*;
 pub use _1_2_depth_testing_view::*;
 pub use _2_stencil_testing::*;
 pub use _3_1_blending_discard::*;
+pub use _3_2_blending_sorted::*;
+pub use _4_face_culling::*;
+pub use _5_1_framebuffers::*;
+pub use _5_2_post_processing_chain::*;
+pub use _6_1_cubemaps_skybox::*;
+pub use _6_2_cubemaps_environment_mapping::*;
+pub use _7_advanced_data::*;
+pub use _8_advanced_glsl_ubo::*;
+pub use _9_1_geometry_shader_houses::*;
+pub use _9_2_geometry_shader_exploding::*;
+pub use _9_3_geometry_shader_normals::*;
+
+pub mod _10_1_instancing_quads;
+
+pub mod _10_2_asteroids;
+
+pub mod _10_3_asteroids_instanced;
+
+pub mod _11_anti_aliasing;
+
+pub mod _1_1_depth_testing;
+
+pub mod _1_2_depth_testing_view;
+
+pub mod _2_stencil_testing;
+
+pub mod _3_1_blending_discard;
+
+pub mod _3_2_blending_sorted;
+
+pub mod _4_face_culling;
+
+pub mod _5_1_framebuffers;
+
+pub mod _5_2_post_processing_chain;
+
+pub mod _6_1_cubemaps_skybox;
+
+pub mod _6_2_cubemaps_environment_mapping;
+
+pub mod _7_advanced_data;
+
+pub mod _8_advanced_glsl_ubo;
+
+pub mod _9_1_geometry_shader_houses;
+
+pub mod _9_2_geometry_shader_exploding;
+
+pub mod _9_3_geometry_shader_normals;
+
+/// tutorials of this chapter in reading order
+pub const TUTORIALS: &[Tutorial] = &[
+    Tutorial { id: "4_1_1", chapter: 4, title: "Depth Testing", main: main_4_1_1 },
+    Tutorial { id: "4_1_2", chapter: 4, title: "Depth Testing View", main: main_4_1_2 },
+    Tutorial { id: "4_2", chapter: 4, title: "Stencil Testing", main: main_4_2 },
+    Tutorial { id: "4_3_1", chapter: 4, title: "Blending Discard", main: main_4_3_1 },
+    Tutorial { id: "4_3_2", chapter: 4, title: "Blending Sorted", main: main_4_3_2 },
+    Tutorial { id: "4_4", chapter: 4, title: "Face Culling", main: main_4_4 },
+    Tutorial { id: "4_5_1", chapter: 4, title: "Framebuffers", main: main_4_5_1 },
+    Tutorial { id: "4_5_2", chapter: 4, title: "Post-Processing Chain", main: main_4_5_2 },
+    Tutorial { id: "4_6_1", chapter: 4, title: "Cubemaps Skybox", main: main_4_6_1 },
+    Tutorial { id: "4_6_2", chapter: 4, title: "Cubemaps Environment Mapping", main: main_4_6_2 },
+    Tutorial { id: "4_7", chapter: 4, title: "Advanced Data", main: main_4_7 },
+    Tutorial { id: "4_8", chapter: 4, title: "Advanced GLSL Uniform Buffers", main: main_4_8 },
+    Tutorial { id: "4_9_1", chapter: 4, title: "Geometry Shader Houses", main: main_4_9_1 },
+    Tutorial { id: "4_9_2", chapter: 4, title: "Geometry Shader Exploding", main: main_4_9_2 },
+    Tutorial { id: "4_9_3", chapter: 4, title: "Geometry Shader Normals", main: main_4_9_3 },
+    Tutorial { id: "4_10_1", chapter: 4, title: "Instancing Quads", main: main_4_10_1 },
+    Tutorial { id: "4_10_2", chapter: 4, title: "Asteroids", main: main_4_10_2 },
+    Tutorial { id: "4_10_3", chapter: 4, title: "Asteroids Instanced", main: main_4_10_3 },
+    Tutorial { id: "4_11", chapter: 4, title: "Anti Aliasing", main: main_4_11 },
+];
@@ -0,0 +1,104 @@
+use std::mem;
+use std::ptr;
+
+use crate::shared::{create_window, window_should_close, Buffer, Shader};
+use cgmath::{vec2, Vector2};
+use glfw::Context;
+
+use crate::tutorial::internal::TutorialGeometry;
+use crate::tutorial::process_events;
+
+// settings
+const SCR_WIDTH: u32 = 1280;
+const SCR_HEIGHT: u32 = 720;
+
+pub fn main_4_10_1() {
+    // glfw: initialize and configure
+    // ------------------------------
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+    glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    #[cfg(target_os = "macos")]
+    glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+
+    // glfw window creation
+    // --------------------
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
+
+    window.make_current();
+    window.set_key_polling(true);
+    window.set_framebuffer_size_polling(true);
+
+    // gl: load all OpenGL function pointers
+    // -------------------------------------
+    gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+    let (shader, quad, _instance_buffer) = unsafe {
+        // build and compile shaders
+        // -------------------------
+        let shader = Shader::new(
+            "src/tutorial/_4_advanced_opengl/shaders/10.1.instancing.vsh",
+            "src/tutorial/_4_advanced_opengl/shaders/10.1.instancing.fsh",
+        );
+
+        // generate a list of 100 quad locations/translation-vectors
+        // ---------------------------------------------------------
+        let offset = 0.1;
+        let mut translations: Vec<Vector2<f32>> = Vec::with_capacity(100);
+        for y in (-10..10).step_by(2) {
+            for x in (-10..10).step_by(2) {
+                translations.push(vec2(x as f32 / 10.0 + offset, y as f32 / 10.0 + offset));
+            }
+        }
+
+        // store instance data in an array buffer
+        // --------------------------------------
+        let instance_buffer = Buffer::new(gl::ARRAY_BUFFER, &translations, gl::STATIC_DRAW);
+
+        // set up vertex data (and buffer(s)) and configure vertex attributes
+        // ------------------------------------------------------------------
+        let quad = TutorialGeometry::new_xyrgb(vec![
+            // positions  // colors
+            -0.05, 0.05, 1.0, 0.0, 0.0, //
+            0.05, -0.05, 0.0, 1.0, 0.0, //
+            -0.05, -0.05, 0.0, 0.0, 1.0, //
+            -0.05, 0.05, 1.0, 0.0, 0.0, //
+            0.05, -0.05, 0.0, 1.0, 0.0, //
+            0.05, 0.05, 0.0, 1.0, 1.0,
+        ]);
+        // also set instance data
+        quad.bind();
+        instance_buffer.bind(); // this attribute comes from a different vertex buffer
+        gl::EnableVertexAttribArray(2);
+        gl::VertexAttribPointer(2, 2, gl::FLOAT, gl::FALSE, mem::size_of::<Vector2<f32>>() as i32, ptr::null());
+        gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+        gl::VertexAttribDivisor(2, 1); // tell OpenGL this is an instanced vertex attribute.
+        gl::BindVertexArray(0);
+
+        (shader, quad, instance_buffer)
+    };
+
+    // render loop
+    // -----------
+    while !window_should_close(&mut glfw, &window) {
+        // events
+        // ------
+        process_events(&mut window, &events);
+
+        // render
+        // ------
+        unsafe {
+            gl::ClearColor(0.1, 0.1, 0.1, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT);
+
+            // draw 100 instanced quads
+            shader.use_program();
+            quad.draw_instanced(100); // 100 quads of 6 vertices each
+        }
+
+        // glfw: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
+        // -------------------------------------------------------------------------------
+        window.swap_buffers();
+        glfw.poll_events();
+    }
+}
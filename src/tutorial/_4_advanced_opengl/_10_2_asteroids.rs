@@ -0,0 +1,150 @@
+use crate::shared::{
+    create_window, get_time, process_events, process_input, window_should_close, Camera, Model, Shader,
+};
+use cgmath::{perspective, vec3, Angle, Deg, InnerSpace, Matrix4, Point3};
+use glfw::Context;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::ffi::CStr;
+
+use crate::c_str;
+
+// settings
+const SCR_WIDTH: u32 = 1280;
+const SCR_HEIGHT: u32 = 720;
+
+pub fn main_4_10_2() {
+    let mut camera = Camera { position: Point3::new(0.0, 0.0, 55.0), ..Camera::default() };
+
+    let mut first_mouse = true;
+    let mut last_x = SCR_WIDTH as f32 / 2.0;
+    let mut last_y = SCR_HEIGHT as f32 / 2.0;
+
+    // timing
+    let mut delta_time: f32; // time between current frame and last frame
+    let mut last_frame: f32 = 0.0;
+
+    // glfw: initialize and configure
+    // ------------------------------
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+    glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    #[cfg(target_os = "macos")]
+    glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+
+    // glfw window creation
+    // --------------------
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
+
+    window.make_current();
+    window.set_framebuffer_size_polling(true);
+    window.set_cursor_pos_polling(true);
+    window.set_scroll_polling(true);
+
+    // tell GLFW to capture our mouse
+    window.set_cursor_mode(glfw::CursorMode::Disabled);
+
+    // gl: load all OpenGL function pointers
+    // -------------------------------------
+    gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+    let (shader, rock, planet, model_matrices) = unsafe {
+        // configure global opengl state
+        // -----------------------------
+        gl::Enable(gl::DEPTH_TEST);
+
+        // build and compile shaders
+        // -------------------------
+        let shader = Shader::new(
+            "src/tutorial/_4_advanced_opengl/shaders/10.2.instancing.vsh",
+            "src/tutorial/_4_advanced_opengl/shaders/10.2.instancing.fsh",
+        );
+
+        // load models
+        // -----------
+        let rock = Model::new("resources/objects/rock/rock.obj");
+        let planet = Model::new("resources/objects/planet/planet.obj");
+
+        // generate a large list of semi-random model transformation matrices
+        // ------------------------------------------------------------------
+        let model_matrices = asteroid_field(1000, 50.0, 2.5);
+
+        (shader, rock, planet, model_matrices)
+    };
+
+    // render loop
+    // -----------
+    while !window_should_close(&mut glfw, &window) {
+        // per-frame time logic
+        // -------------------
+        let current_frame = get_time(&glfw) as f32;
+        delta_time = current_frame - last_frame;
+        last_frame = current_frame;
+
+        // events
+        // ------
+        process_events(&events, &mut first_mouse, &mut last_x, &mut last_y, &mut camera);
+
+        // input
+        // -----
+        process_input(&mut window, delta_time, &mut camera);
+
+        // render
+        // ------
+        unsafe {
+            gl::ClearColor(0.1, 0.1, 0.1, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+
+            // configure transformation matrices
+            let projection: Matrix4<f32> =
+                perspective(Deg(camera.zoom), SCR_WIDTH as f32 / SCR_HEIGHT as f32, 0.1, 100.0);
+            let view = camera.get_view_matrix();
+            shader.use_program();
+            shader.set_mat4(c_str!("projection"), &projection);
+            shader.set_mat4(c_str!("view"), &view);
+
+            // draw planet
+            let mut model = Matrix4::<f32>::from_translation(vec3(0.0, -3.0, 0.0));
+            model = model * Matrix4::from_scale(4.0);
+            shader.set_mat4(c_str!("model"), &model);
+            planet.draw(&shader);
+
+            // draw meteorites, one draw call per rock
+            for model in &model_matrices {
+                shader.set_mat4(c_str!("model"), model);
+                rock.draw(&shader);
+            }
+        }
+
+        // glfw: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
+        // -------------------------------------------------------------------------------
+        window.swap_buffers();
+        glfw.poll_events();
+    }
+}
+
+/// `amount` randomly scaled and rotated model matrices along a ring of the given
+/// `radius`, displaced by up to `offset` in each direction. The random numbers
+/// are seeded, so the field looks the same on every run (and golden frame).
+pub fn asteroid_field(amount: usize, radius: f32, offset: f32) -> Vec<Matrix4<f32>> {
+    let mut rng = StdRng::seed_from_u64(0);
+    (0..amount)
+        .map(|i| {
+            // 1. translation: displace along circle with 'radius' in range [-offset, offset]
+            let angle = Deg(i as f32 / amount as f32 * 360.0);
+            let mut displacement = || rng.gen_range(-offset, offset);
+            let x = angle.sin() * radius + displacement();
+            let y = displacement() * 0.4; // keep height of asteroid field smaller compared to width of x and z
+            let z = angle.cos() * radius + displacement();
+            let mut model = Matrix4::from_translation(vec3(x, y, z));
+
+            // 2. scale: scale between 0.05 and 0.25
+            let scale = rng.gen_range(0.05, 0.25);
+            model = model * Matrix4::from_scale(scale);
+
+            // 3. rotation: add random rotation around a (semi)randomly picked rotation axis vector
+            let rot_angle = Deg(rng.gen_range(0.0, 360.0));
+            model * Matrix4::from_axis_angle(vec3(0.4, 0.6, 0.8).normalize(), rot_angle)
+        })
+        .collect()
+}
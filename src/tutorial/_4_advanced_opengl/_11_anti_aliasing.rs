@@ -0,0 +1,216 @@
+use crate::shared::{
+    create_window_with_samples, get_time, process_events_with, process_input, window_should_close, Camera, Framebuffer,
+    Shader,
+};
+use cgmath::{perspective, Deg, Matrix4, Point3, SquareMatrix};
+use glfw::{Action, Context, Key};
+use std::ffi::CStr;
+
+use crate::c_str;
+use crate::tutorial::internal::TutorialGeometry;
+
+// settings
+const SCR_WIDTH: u32 = 1280;
+const SCR_HEIGHT: u32 = 720;
+
+/// samples per pixel of both the window and the offscreen framebuffer
+const SAMPLES: i32 = 4;
+
+/// ways to anti-alias the scene, selected with the number keys
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    /// render straight into the multisampled window
+    Window,
+    /// render into a multisampled framebuffer, resolve it into a regular one with a blit and post-process that
+    OffscreenBlit,
+    /// render into a multisampled framebuffer and resolve it in the post-processing shader
+    OffscreenShader,
+}
+
+pub fn main_4_11() {
+    let mut camera = Camera { position: Point3::new(0.0, 0.0, 3.0), ..Camera::default() };
+
+    let mut first_mouse = true;
+    let mut last_x: f32 = SCR_WIDTH as f32 / 2.0;
+    let mut last_y: f32 = SCR_HEIGHT as f32 / 2.0;
+
+    // timing
+    let mut delta_time: f32; // time between current frame and last frame
+    let mut last_frame: f32 = 0.0;
+
+    // glfw: initialize and configure
+    // ------------------------------
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+    glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    #[cfg(target_os = "macos")]
+    glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+
+    // glfw window creation, with a multisampled back buffer
+    // -----------------------------------------------------
+    let (mut window, events) =
+        create_window_with_samples(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL", SAMPLES as u32);
+
+    window.make_current();
+    window.set_framebuffer_size_polling(true);
+    window.set_cursor_pos_polling(true);
+    window.set_scroll_polling(true);
+
+    // tell GLFW to capture our mouse
+    window.set_cursor_mode(glfw::CursorMode::Disabled);
+
+    // gl: load all OpenGL function pointers
+    // -------------------------------------
+    gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+    let (shader, post_shader, resolve_shader, cube, quad, mut multisampled, mut intermediate) = unsafe {
+        // configure global opengl state
+        // -----------------------------
+        gl::Enable(gl::DEPTH_TEST);
+        gl::Enable(gl::MULTISAMPLE); // enabled by default on some drivers, but not all so always enable to make sure
+
+        // build and compile shaders
+        // -------------------------
+        let shader = Shader::new(
+            "src/tutorial/_4_advanced_opengl/shaders/11.anti_aliasing.vsh",
+            "src/tutorial/_4_advanced_opengl/shaders/11.anti_aliasing.fsh",
+        );
+        let post_shader = Shader::new(
+            "src/tutorial/_4_advanced_opengl/shaders/11.aa_post.vsh",
+            "src/tutorial/_4_advanced_opengl/shaders/11.aa_post.fsh",
+        );
+        let resolve_shader = Shader::new(
+            "src/tutorial/_4_advanced_opengl/shaders/11.aa_post.vsh",
+            "src/tutorial/_4_advanced_opengl/shaders/11.aa_resolve.fsh",
+        );
+
+        // set up vertex data (and buffer(s)) and configure vertex attributes
+        // ------------------------------------------------------------------
+        let cube = TutorialGeometry::new_xyz(vec![
+            // positions
+            -0.5, -0.5, -0.5, 0.5, -0.5, -0.5, 0.5, 0.5, -0.5, //
+            0.5, 0.5, -0.5, -0.5, 0.5, -0.5, -0.5, -0.5, -0.5, //
+            -0.5, -0.5, 0.5, 0.5, -0.5, 0.5, 0.5, 0.5, 0.5, //
+            0.5, 0.5, 0.5, -0.5, 0.5, 0.5, -0.5, -0.5, 0.5, //
+            -0.5, 0.5, 0.5, -0.5, 0.5, -0.5, -0.5, -0.5, -0.5, //
+            -0.5, -0.5, -0.5, -0.5, -0.5, 0.5, -0.5, 0.5, 0.5, //
+            0.5, 0.5, 0.5, 0.5, 0.5, -0.5, 0.5, -0.5, -0.5, //
+            0.5, -0.5, -0.5, 0.5, -0.5, 0.5, 0.5, 0.5, 0.5, //
+            -0.5, -0.5, -0.5, 0.5, -0.5, -0.5, 0.5, -0.5, 0.5, //
+            0.5, -0.5, 0.5, -0.5, -0.5, 0.5, -0.5, -0.5, -0.5, //
+            -0.5, 0.5, -0.5, 0.5, 0.5, -0.5, 0.5, 0.5, 0.5, //
+            0.5, 0.5, 0.5, -0.5, 0.5, 0.5, -0.5, 0.5, -0.5,
+        ]);
+
+        // vertex attributes for a quad that fills the entire screen in Normalized Device Coordinates.
+        let quad = TutorialGeometry::new_xyuv(vec![
+            // positions // texCoords
+            -1.0, 1.0, 0.0, 1.0, -1.0, -1.0, 0.0, 0.0, 1.0, -1.0, 1.0, 0.0, //
+            -1.0, 1.0, 0.0, 1.0, 1.0, -1.0, 1.0, 0.0, 1.0, 1.0, 1.0, 1.0,
+        ]);
+
+        // configure MSAA framebuffer
+        // --------------------------
+        let (width, height) = window.get_framebuffer_size();
+        let multisampled = Framebuffer::builder(width, height)
+            .samples(SAMPLES)
+            .color_texture(gl::RGB)
+            .depth_stencil_renderbuffer()
+            .build()
+            .unwrap_or_else(|e| panic!("{}", e));
+        // configure second post-processing framebuffer, only the color buffer is needed
+        let intermediate =
+            Framebuffer::builder(width, height).color_texture(gl::RGB).build().unwrap_or_else(|e| panic!("{}", e));
+
+        // shader configuration
+        // --------------------
+        post_shader.use_program();
+        post_shader.set_int(c_str!("screenTexture"), 0);
+        resolve_shader.use_program();
+        resolve_shader.set_int(c_str!("screenTexture"), 0);
+        resolve_shader.set_int(c_str!("samples"), SAMPLES);
+
+        (shader, post_shader, resolve_shader, cube, quad, multisampled, intermediate)
+    };
+
+    let modes = [(Key::Num1, Mode::Window), (Key::Num2, Mode::OffscreenBlit), (Key::Num3, Mode::OffscreenShader)];
+    let mut mode = Mode::Window;
+
+    // render loop
+    // -----------
+    while !window_should_close(&mut glfw, &window) {
+        // per-frame time logic
+        // --------------------
+        let current_frame = get_time(&glfw) as f32;
+        delta_time = current_frame - last_frame;
+        last_frame = current_frame;
+
+        // events
+        // ------
+        process_events_with(&events, &mut first_mouse, &mut last_x, &mut last_y, &mut camera, |event| unsafe {
+            multisampled.handle_event(event).unwrap_or_else(|e| panic!("{}", e));
+            intermediate.handle_event(event).unwrap_or_else(|e| panic!("{}", e));
+        });
+
+        // input
+        // -----
+        process_input(&mut window, delta_time, &mut camera);
+        for (key, key_mode) in &modes {
+            if window.get_key(*key) == Action::Press && mode != *key_mode {
+                mode = *key_mode;
+                println!("anti-aliasing: {:?}", mode);
+            }
+        }
+
+        // render
+        // ------
+        unsafe {
+            let (width, height) = window.get_framebuffer_size();
+
+            // 1. draw scene as normal, into the multisampled window or framebuffer
+            if mode == Mode::Window {
+                Framebuffer::bind_default(width, height);
+            } else {
+                multisampled.bind();
+            }
+            gl::ClearColor(0.1, 0.1, 0.1, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+            gl::Enable(gl::DEPTH_TEST);
+
+            // set transformation matrices
+            shader.use_program();
+            let projection: Matrix4<f32> = perspective(Deg(camera.zoom), width as f32 / height as f32, 0.1, 1000.0);
+            shader.set_mat4(c_str!("projection"), &projection);
+            shader.set_mat4(c_str!("view"), &camera.get_view_matrix());
+            shader.set_mat4(c_str!("model"), &Matrix4::identity());
+            cube.draw();
+
+            if mode != Mode::Window {
+                // 2. now render quad with scene's visuals as its texture image
+                if mode == Mode::OffscreenBlit {
+                    // resolve the multisampled buffer into the normal color buffer of the intermediate FBO
+                    multisampled.blit_to(&intermediate, gl::COLOR_BUFFER_BIT);
+                    post_shader.use_program();
+                    gl::ActiveTexture(gl::TEXTURE0);
+                    gl::BindTexture(gl::TEXTURE_2D, intermediate.color_texture(0));
+                } else {
+                    // sample the multisampled texture directly
+                    resolve_shader.use_program();
+                    gl::ActiveTexture(gl::TEXTURE0);
+                    gl::BindTexture(multisampled.texture_target(), multisampled.color_texture(0));
+                }
+
+                Framebuffer::bind_default(width, height);
+                gl::ClearColor(1.0, 1.0, 1.0, 1.0);
+                gl::Clear(gl::COLOR_BUFFER_BIT);
+                gl::Disable(gl::DEPTH_TEST);
+                quad.draw();
+            }
+        }
+
+        // glfw: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
+        // -------------------------------------------------------------------------------
+        window.swap_buffers();
+        glfw.poll_events();
+    }
+}
@@ -0,0 +1,233 @@
+use crate::shared::{
+    create_window, get_time, load_texture, process_events_with, process_input, window_should_close, Camera, Shader,
+    TransparentQueue, WeightedBlendedOit,
+};
+use cgmath::{perspective, vec3, Deg, Matrix4, Point3, SquareMatrix, Vector3};
+use glfw::{Action, Context, Key};
+use std::ffi::CStr;
+
+use crate::c_str;
+use crate::tutorial::internal::TutorialGeometry;
+
+// settings
+const SCR_WIDTH: u32 = 1280;
+const SCR_HEIGHT: u32 = 720;
+
+/// how the semi-transparent windows are drawn, selected with the number keys
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    /// back-to-front through a `TransparentQueue`
+    Sorted,
+    /// in the order they are stored in, showing what goes wrong without sorting
+    Unsorted,
+    /// weighted blended order-independent transparency
+    WeightedBlended,
+}
+
+pub fn main_4_3_2() {
+    let mut camera = Camera { position: Point3::new(0.0, 0.0, 3.0), ..Camera::default() };
+
+    let mut first_mouse = true;
+    let mut last_x: f32 = SCR_WIDTH as f32 / 2.0;
+    let mut last_y: f32 = SCR_HEIGHT as f32 / 2.0;
+
+    // timing
+    let mut delta_time: f32; // time between current frame and last frame
+    let mut last_frame: f32 = 0.0;
+
+    // glfw: initialize and configure
+    // ------------------------------
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+    glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    #[cfg(target_os = "macos")]
+    glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+
+    // glfw window creation
+    // --------------------
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
+
+    window.make_current();
+    window.set_framebuffer_size_polling(true);
+    window.set_cursor_pos_polling(true);
+    window.set_scroll_polling(true);
+
+    // tell GLFW to capture our mouse
+    window.set_cursor_mode(glfw::CursorMode::Disabled);
+
+    // gl: load all OpenGL function pointers
+    // -------------------------------------
+    gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+    let (shader, oit_shader, cube, plane, window_quad, cube_texture, floor_texture, window_texture, mut oit) = unsafe {
+        // configure global opengl state
+        // -----------------------------
+        gl::Enable(gl::DEPTH_TEST);
+        gl::Enable(gl::BLEND);
+        gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
+
+        // build and compile shaders
+        // -------------------------
+        let shader = Shader::new(
+            "src/tutorial/_4_advanced_opengl/shaders/3.2.blending.vsh",
+            "src/tutorial/_4_advanced_opengl/shaders/3.2.blending.fsh",
+        );
+        let oit_shader = Shader::new(
+            "src/tutorial/_4_advanced_opengl/shaders/3.2.blending.vsh",
+            "src/tutorial/_4_advanced_opengl/shaders/3.2.blending_oit.fsh",
+        );
+
+        // set up vertex data (and buffer(s)) and configure vertex attributes
+        // ------------------------------------------------------------------
+        let cube = TutorialGeometry::new_xyzuv(vec![
+            // positions       // texture Coords
+            -0.5, -0.5, -0.5, 0.0, 0.0, 0.5, -0.5, -0.5, 1.0, 0.0, 0.5, 0.5, -0.5, 1.0, 1.0, 0.5, 0.5, -0.5, 1.0, 1.0,
+            -0.5, 0.5, -0.5, 0.0, 1.0, -0.5, -0.5, -0.5, 0.0, 0.0, //
+            -0.5, -0.5, 0.5, 0.0, 0.0, 0.5, -0.5, 0.5, 1.0, 0.0, 0.5, 0.5, 0.5, 1.0, 1.0, 0.5, 0.5, 0.5, 1.0, 1.0,
+            -0.5, 0.5, 0.5, 0.0, 1.0, -0.5, -0.5, 0.5, 0.0, 0.0, //
+            -0.5, 0.5, 0.5, 1.0, 0.0, -0.5, 0.5, -0.5, 1.0, 1.0, -0.5, -0.5, -0.5, 0.0, 1.0, -0.5, -0.5, -0.5, 0.0,
+            1.0, -0.5, -0.5, 0.5, 0.0, 0.0, -0.5, 0.5, 0.5, 1.0, 0.0, //
+            0.5, 0.5, 0.5, 1.0, 0.0, 0.5, 0.5, -0.5, 1.0, 1.0, 0.5, -0.5, -0.5, 0.0, 1.0, 0.5, -0.5, -0.5, 0.0, 1.0,
+            0.5, -0.5, 0.5, 0.0, 0.0, 0.5, 0.5, 0.5, 1.0, 0.0, //
+            -0.5, -0.5, -0.5, 0.0, 1.0, 0.5, -0.5, -0.5, 1.0, 1.0, 0.5, -0.5, 0.5, 1.0, 0.0, 0.5, -0.5, 0.5, 1.0, 0.0,
+            -0.5, -0.5, 0.5, 0.0, 0.0, -0.5, -0.5, -0.5, 0.0, 1.0, //
+            -0.5, 0.5, -0.5, 0.0, 1.0, 0.5, 0.5, -0.5, 1.0, 1.0, 0.5, 0.5, 0.5, 1.0, 0.0, 0.5, 0.5, 0.5, 1.0, 0.0,
+            -0.5, 0.5, 0.5, 0.0, 0.0, -0.5, 0.5, -0.5, 0.0, 1.0,
+        ]);
+
+        let plane = TutorialGeometry::new_xyzuv(vec![
+            // positions       // texture Coords (note we set these higher than 1 (together with GL_REPEAT as texture wrapping mode). this will cause the floor texture to repeat)
+            5.0, -0.5, 5.0, 2.0, 0.0, -5.0, -0.5, 5.0, 0.0, 0.0, -5.0, -0.5, -5.0, 0.0, 2.0, //
+            5.0, -0.5, 5.0, 2.0, 0.0, -5.0, -0.5, -5.0, 0.0, 2.0, 5.0, -0.5, -5.0, 2.0, 2.0,
+        ]);
+
+        let window_quad = TutorialGeometry::new_xyzuv(vec![
+            // positions      // texture Coords (swapped y coordinates because texture is flipped upside down)
+            0.0, 0.5, 0.0, 0.0, 0.0, 0.0, -0.5, 0.0, 0.0, 1.0, 1.0, -0.5, 0.0, 1.0, 1.0, //
+            0.0, 0.5, 0.0, 0.0, 0.0, 1.0, -0.5, 0.0, 1.0, 1.0, 1.0, 0.5, 0.0, 1.0, 0.0,
+        ]);
+
+        // load textures
+        // -------------
+        let cube_texture = load_texture("resources/textures/marble.jpg");
+        let floor_texture = load_texture("resources/textures/metal.png");
+        let window_texture = load_texture("resources/textures/window.png");
+        // don't let the repeating texture bleed the opposite edge into the borders of the window
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as i32);
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as i32);
+
+        // weighted blended OIT accumulates into its own framebuffer
+        // ----------------------------------------------------------
+        let (width, height) = window.get_framebuffer_size();
+        let oit = WeightedBlendedOit::new(width, height).unwrap_or_else(|e| panic!("{}", e));
+
+        // shader configuration
+        // --------------------
+        shader.use_program();
+        shader.set_int(c_str!("texture1"), 0);
+        oit_shader.use_program();
+        oit_shader.set_int(c_str!("texture1"), 0);
+
+        (shader, oit_shader, cube, plane, window_quad, cube_texture, floor_texture, window_texture, oit)
+    };
+
+    // transparent window locations
+    // ----------------------------
+    let windows = [
+        vec3(-1.5, 0.0, -0.48),
+        vec3(1.5, 0.0, 0.51),
+        vec3(0.0, 0.0, 0.7),
+        vec3(-0.3, 0.0, -2.3),
+        vec3(0.5, 0.0, -0.6),
+    ];
+    let mut queue = TransparentQueue::new();
+
+    let modes = [(Key::Num1, Mode::Sorted), (Key::Num2, Mode::Unsorted), (Key::Num3, Mode::WeightedBlended)];
+    let mut mode = Mode::Sorted;
+
+    // render loop
+    // -----------
+    while !window_should_close(&mut glfw, &window) {
+        // per-frame time logic
+        // --------------------
+        let current_frame = get_time(&glfw) as f32;
+        delta_time = current_frame - last_frame;
+        last_frame = current_frame;
+
+        // events
+        // ------
+        process_events_with(&events, &mut first_mouse, &mut last_x, &mut last_y, &mut camera, |event| unsafe {
+            oit.handle_event(event).unwrap_or_else(|e| panic!("{}", e));
+        });
+
+        // input
+        // -----
+        process_input(&mut window, delta_time, &mut camera);
+        for (key, key_mode) in &modes {
+            if window.get_key(*key) == Action::Press && mode != *key_mode {
+                mode = *key_mode;
+                println!("transparency: {:?}", mode);
+            }
+        }
+
+        // render
+        // ------
+        unsafe {
+            let (width, height) = window.get_framebuffer_size();
+            gl::ClearColor(0.1, 0.1, 0.1, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+
+            // draw opaque objects first
+            let projection: Matrix4<f32> = perspective(Deg(camera.zoom), width as f32 / height as f32, 0.1, 100.0);
+            let view = camera.get_view_matrix();
+            for shader in &[&shader, &oit_shader] {
+                shader.use_program();
+                shader.set_mat4(c_str!("projection"), &projection);
+                shader.set_mat4(c_str!("view"), &view);
+            }
+            shader.use_program();
+            // cubes
+            cube.bind();
+            gl::ActiveTexture(gl::TEXTURE0);
+            gl::BindTexture(gl::TEXTURE_2D, cube_texture);
+            shader.set_mat4(c_str!("model"), &Matrix4::from_translation(vec3(-1.0, 0.0, -1.0)));
+            cube.draw();
+            shader.set_mat4(c_str!("model"), &Matrix4::from_translation(vec3(2.0, 0.0, 0.0)));
+            cube.draw();
+            // floor
+            plane.bind();
+            gl::BindTexture(gl::TEXTURE_2D, floor_texture);
+            shader.set_mat4(c_str!("model"), &Matrix4::identity());
+            plane.draw();
+
+            // then the windows
+            window_quad.bind();
+            gl::BindTexture(gl::TEXTURE_2D, window_texture);
+            let draw_window = |shader: &Shader, position: &Vector3<f32>| {
+                shader.set_mat4(c_str!("model"), &Matrix4::from_translation(*position));
+                window_quad.draw();
+            };
+            match mode {
+                Mode::Sorted => {
+                    queue.clear();
+                    for position in &windows {
+                        queue.push(*position, position);
+                    }
+                    queue.draw(camera.position, |position| draw_window(&shader, position));
+                }
+                Mode::Unsorted => windows.iter().for_each(|position| draw_window(&shader, position)),
+                Mode::WeightedBlended => {
+                    oit.begin(width, height);
+                    oit_shader.use_program();
+                    windows.iter().for_each(|position| draw_window(&oit_shader, position));
+                    oit.composite(width, height);
+                }
+            }
+        }
+
+        // glfw: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
+        // -------------------------------------------------------------------------------
+        window.swap_buffers();
+        glfw.poll_events();
+    }
+}
@@ -0,0 +1,149 @@
+use crate::shared::{
+    check_winding_unindexed, create_window, fix_winding, get_time, interleaved_vertices, load_texture, process_events,
+    process_input, sequential_indices, window_should_close, Camera, Mesh, Shader, Texture, CUBE_VERTICES,
+};
+use cgmath::{perspective, vec3, Deg, Matrix4, Point3};
+use glfw::{Action, Context, Key};
+use std::ffi::CStr;
+
+use crate::c_str;
+
+// settings
+const SCR_WIDTH: u32 = 1280;
+const SCR_HEIGHT: u32 = 720;
+
+pub fn main_4_4() {
+    let mut camera = Camera { position: Point3::new(0.0, 0.0, 3.0), ..Camera::default() };
+
+    let mut first_mouse = true;
+    let mut last_x: f32 = SCR_WIDTH as f32 / 2.0;
+    let mut last_y: f32 = SCR_HEIGHT as f32 / 2.0;
+
+    // timing
+    let mut delta_time: f32; // time between current frame and last frame
+    let mut last_frame: f32 = 0.0;
+
+    // glfw: initialize and configure
+    // ------------------------------
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+    glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    #[cfg(target_os = "macos")]
+    glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+
+    // glfw window creation
+    // --------------------
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
+
+    window.make_current();
+    window.set_framebuffer_size_polling(true);
+    window.set_cursor_pos_polling(true);
+    window.set_scroll_polling(true);
+
+    // tell GLFW to capture our mouse
+    window.set_cursor_mode(glfw::CursorMode::Disabled);
+
+    // gl: load all OpenGL function pointers
+    // -------------------------------------
+    gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+    let (shader, copied_cube, fixed_cube) = unsafe {
+        // configure global opengl state
+        // -----------------------------
+        gl::Enable(gl::DEPTH_TEST);
+        gl::Enable(gl::CULL_FACE);
+        gl::CullFace(gl::BACK);
+        gl::FrontFace(gl::CCW); // the default, counter-clockwise triangles are front facing
+
+        // build and compile our shader program
+        // ------------------------------------
+        let shader = Shader::new(
+            "src/tutorial/_4_advanced_opengl/shaders/4.face_culling.vsh",
+            "src/tutorial/_4_advanced_opengl/shaders/4.face_culling.fsh",
+        );
+
+        // set up vertex data: the cube as it was copied around, and the same cube with its winding fixed
+        // ----------------------------------------------------------------------------------------------
+        let vertices = interleaved_vertices(&CUBE_VERTICES);
+        let misoriented = check_winding_unindexed(&vertices);
+        println!(
+            "{} of {} cube triangles are wound clockwise: {:?}",
+            misoriented.len(),
+            vertices.len() / 3,
+            misoriented
+        );
+
+        let textures = vec![Texture {
+            id: load_texture("resources/textures/marble.jpg"),
+            type_: "texture_diffuse".into(),
+            path: "resources/textures/marble.jpg".into(),
+        }];
+        let indices = sequential_indices(vertices.len());
+        let mut fixed_indices = indices.clone();
+        fix_winding(&vertices, &mut fixed_indices);
+
+        let copied_cube = Mesh::new(interleaved_vertices(&CUBE_VERTICES), indices, textures.clone());
+        let fixed_cube = Mesh::new(vertices, fixed_indices, textures);
+
+        (shader, copied_cube, fixed_cube)
+    };
+
+    let modes = [(Key::Num1, None), (Key::Num2, Some(gl::BACK)), (Key::Num3, Some(gl::FRONT))];
+
+    // render loop
+    // -----------
+    while !window_should_close(&mut glfw, &window) {
+        // per-frame time logic
+        // --------------------
+        let current_frame = get_time(&glfw) as f32;
+        delta_time = current_frame - last_frame;
+        last_frame = current_frame;
+
+        // events
+        // ------
+        process_events(&events, &mut first_mouse, &mut last_x, &mut last_y, &mut camera);
+
+        // input
+        // -----
+        process_input(&mut window, delta_time, &mut camera);
+        // 1 disables culling, 2 culls back faces and 3 front faces
+        for (key, cull_face) in &modes {
+            if window.get_key(*key) == Action::Press {
+                unsafe {
+                    match cull_face {
+                        Some(face) => {
+                            gl::Enable(gl::CULL_FACE);
+                            gl::CullFace(*face);
+                        }
+                        None => gl::Disable(gl::CULL_FACE),
+                    }
+                }
+            }
+        }
+
+        // render
+        // ------
+        unsafe {
+            gl::ClearColor(0.1, 0.1, 0.1, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+
+            shader.use_program();
+            let projection: Matrix4<f32> =
+                perspective(Deg(camera.zoom), SCR_WIDTH as f32 / SCR_HEIGHT as f32, 0.1, 100.0);
+            shader.set_mat4(c_str!("view"), &camera.get_view_matrix());
+            shader.set_mat4(c_str!("projection"), &projection);
+
+            // the copied cube on the left: with culling enabled, some of its outside faces disappear
+            shader.set_mat4(c_str!("model"), &Matrix4::from_translation(vec3(-0.8, 0.0, 0.0)));
+            copied_cube.draw(&shader);
+            // the fixed cube on the right: only its inside (back) faces get culled
+            shader.set_mat4(c_str!("model"), &Matrix4::from_translation(vec3(0.8, 0.0, 0.0)));
+            fixed_cube.draw(&shader);
+        }
+
+        // glfw: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
+        // -------------------------------------------------------------------------------
+        window.swap_buffers();
+        glfw.poll_events();
+    }
+}
@@ -0,0 +1,177 @@
+use crate::shared::{
+    create_window, get_time, load_texture, process_events_with, process_input, window_should_close, Camera,
+    PostProcessChain, Shader,
+};
+use cgmath::{perspective, vec3, Deg, Matrix4, Point3, SquareMatrix};
+use glfw::Context;
+use std::ffi::CStr;
+
+use crate::c_str;
+use crate::tutorial::internal::TutorialGeometry;
+
+// settings
+const SCR_WIDTH: u32 = 1280;
+const SCR_HEIGHT: u32 = 720;
+
+pub fn main_4_5_2() {
+    let mut camera = Camera { position: Point3::new(0.0, 0.0, 3.0), ..Camera::default() };
+
+    let mut first_mouse = true;
+    let mut last_x: f32 = SCR_WIDTH as f32 / 2.0;
+    let mut last_y: f32 = SCR_HEIGHT as f32 / 2.0;
+
+    // timing
+    let mut delta_time: f32; // time between current frame and last frame
+    let mut last_frame: f32 = 0.0;
+
+    // glfw: initialize and configure
+    // ------------------------------
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+    glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    #[cfg(target_os = "macos")]
+    glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+
+    // glfw window creation
+    // --------------------
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
+
+    window.make_current();
+    window.set_framebuffer_size_polling(true);
+    window.set_cursor_pos_polling(true);
+    window.set_scroll_polling(true);
+
+    // tell GLFW to capture our mouse
+    window.set_cursor_mode(glfw::CursorMode::Disabled);
+
+    // gl: load all OpenGL function pointers
+    // -------------------------------------
+    gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+    let (shader, cube, plane, cube_texture, floor_texture, mut chain) = unsafe {
+        // build and compile our shader programs
+        // ------------------------------------
+        let shader = Shader::new(
+            "src/tutorial/_4_advanced_opengl/shaders/5.1.framebuffers.vsh",
+            "src/tutorial/_4_advanced_opengl/shaders/5.1.framebuffers.fsh",
+        );
+
+        // set up vertex data (and buffer(s)) and configure vertex attributes
+        // ------------------------------------------------------------------
+        let cube = TutorialGeometry::new_xyzuv(vec![
+            // positions       // texture Coords
+            -0.5, -0.5, -0.5, 0.0, 0.0, 0.5, -0.5, -0.5, 1.0, 0.0, 0.5, 0.5, -0.5, 1.0, 1.0, 0.5, 0.5, -0.5, 1.0, 1.0,
+            -0.5, 0.5, -0.5, 0.0, 1.0, -0.5, -0.5, -0.5, 0.0, 0.0, //
+            -0.5, -0.5, 0.5, 0.0, 0.0, 0.5, -0.5, 0.5, 1.0, 0.0, 0.5, 0.5, 0.5, 1.0, 1.0, 0.5, 0.5, 0.5, 1.0, 1.0,
+            -0.5, 0.5, 0.5, 0.0, 1.0, -0.5, -0.5, 0.5, 0.0, 0.0, //
+            -0.5, 0.5, 0.5, 1.0, 0.0, -0.5, 0.5, -0.5, 1.0, 1.0, -0.5, -0.5, -0.5, 0.0, 1.0, -0.5, -0.5, -0.5, 0.0,
+            1.0, -0.5, -0.5, 0.5, 0.0, 0.0, -0.5, 0.5, 0.5, 1.0, 0.0, //
+            0.5, 0.5, 0.5, 1.0, 0.0, 0.5, 0.5, -0.5, 1.0, 1.0, 0.5, -0.5, -0.5, 0.0, 1.0, 0.5, -0.5, -0.5, 0.0, 1.0,
+            0.5, -0.5, 0.5, 0.0, 0.0, 0.5, 0.5, 0.5, 1.0, 0.0, //
+            -0.5, -0.5, -0.5, 0.0, 1.0, 0.5, -0.5, -0.5, 1.0, 1.0, 0.5, -0.5, 0.5, 1.0, 0.0, 0.5, -0.5, 0.5, 1.0, 0.0,
+            -0.5, -0.5, 0.5, 0.0, 0.0, -0.5, -0.5, -0.5, 0.0, 1.0, //
+            -0.5, 0.5, -0.5, 0.0, 1.0, 0.5, 0.5, -0.5, 1.0, 1.0, 0.5, 0.5, 0.5, 1.0, 0.0, 0.5, 0.5, 0.5, 1.0, 0.0,
+            -0.5, 0.5, 0.5, 0.0, 0.0, -0.5, 0.5, -0.5, 0.0, 1.0,
+        ]);
+
+        let plane = TutorialGeometry::new_xyzuv(vec![
+            // positions       // texture Coords (note we set these higher than 1 (together with GL_REPEAT as texture wrapping mode). this will cause the floor texture to repeat)
+            5.0, -0.5, 5.0, 2.0, 0.0, -5.0, -0.5, 5.0, 0.0, 0.0, -5.0, -0.5, -5.0, 0.0, 2.0, //
+            5.0, -0.5, 5.0, 2.0, 0.0, -5.0, -0.5, -5.0, 0.0, 2.0, 5.0, -0.5, -5.0, 2.0, 2.0,
+        ]);
+
+        // load textures
+        // -------------
+        let cube_texture = load_texture("resources/textures/container.jpg");
+        let floor_texture = load_texture("resources/textures/metal.png");
+
+        // shader configuration
+        // --------------------
+        shader.use_program();
+        shader.set_int(c_str!("texture1"), 0);
+
+        // post-processing configuration
+        // -----------------------------
+        // the scene is rendered into an HDR framebuffer, then every enabled pass
+        // runs over the result of the previous one
+        let (width, height) = window.get_framebuffer_size();
+        let mut chain = PostProcessChain::with_default_passes(width, height).unwrap_or_else(|e| panic!("{}", e));
+        // the scene isn't HDR, so a low exposure makes the tone mapping easier to spot
+        if let Some(pass) = chain.pass_mut("tone mapping") {
+            pass.set_param("exposure", &[0.5]);
+        }
+        println!("toggle post-processing passes with keys 1-{}:", chain.passes().len());
+        for pass in chain.passes() {
+            println!("  {:?}: {}", pass.key.unwrap(), pass.name);
+        }
+
+        (shader, cube, plane, cube_texture, floor_texture, chain)
+    };
+
+    // draw as wireframe
+    // unsafe { gl::PolygonMode(gl::FRONT_AND_BACK, gl::LINE) };
+
+    // render loop
+    // -----------
+    while !window_should_close(&mut glfw, &window) {
+        // per-frame time logic
+        // --------------------
+        let current_frame = get_time(&glfw) as f32;
+        delta_time = current_frame - last_frame;
+        last_frame = current_frame;
+
+        // events
+        // ------
+        process_events_with(&events, &mut first_mouse, &mut last_x, &mut last_y, &mut camera, |event| unsafe {
+            // keep the offscreen targets the same size as the window
+            chain.handle_event(event).unwrap_or_else(|e| panic!("{}", e));
+        });
+
+        // input
+        // -----
+        process_input(&mut window, delta_time, &mut camera);
+        chain.process_input(&window);
+
+        // render
+        // ------
+        unsafe {
+            // bind to the chain's framebuffer and draw scene as we normally would to color texture
+            chain.begin_scene();
+            gl::Enable(gl::DEPTH_TEST); // enable depth testing (is disabled for rendering screen-space quad)
+
+            // make sure we clear the framebuffer's content
+            gl::ClearColor(0.1, 0.1, 0.1, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+
+            shader.use_program();
+            let projection: Matrix4<f32> =
+                perspective(Deg(camera.zoom), SCR_WIDTH as f32 / SCR_HEIGHT as f32, 0.1, 100.0);
+            let view = camera.get_view_matrix();
+            let mut model: Matrix4<f32>;
+            shader.set_mat4(c_str!("projection"), &projection);
+            shader.set_mat4(c_str!("view"), &view);
+            // cubes
+            gl::ActiveTexture(gl::TEXTURE0);
+            gl::BindTexture(gl::TEXTURE_2D, cube_texture);
+            model = Matrix4::from_translation(vec3(-1.0, 0.0, -1.0));
+            shader.set_mat4(c_str!("model"), &model);
+            cube.draw();
+            model = Matrix4::from_translation(vec3(2.0, 0.0, 0.0));
+            shader.set_mat4(c_str!("model"), &model);
+            cube.draw();
+            // floor
+            gl::BindTexture(gl::TEXTURE_2D, floor_texture);
+            shader.set_mat4(c_str!("model"), &Matrix4::identity());
+            plane.draw();
+
+            // now run the enabled passes, the last one draws into the default framebuffer
+            let (width, height) = window.get_framebuffer_size();
+            chain.apply(width, height);
+        }
+
+        // glfw: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
+        // -------------------------------------------------------------------------------
+        window.swap_buffers();
+        glfw.poll_events();
+    }
+}
@@ -0,0 +1,168 @@
+use crate::shared::{
+    create_window, get_time, process_events, process_input, window_should_close, Camera, Shader, Skybox,
+};
+use cgmath::{perspective, Deg, EuclideanSpace, Matrix4, Point3, SquareMatrix};
+use glfw::{Action, Context, Key};
+use std::ffi::CStr;
+
+use crate::c_str;
+use crate::tutorial::internal::TutorialGeometry;
+
+// settings
+const SCR_WIDTH: u32 = 1280;
+const SCR_HEIGHT: u32 = 720;
+
+pub fn main_4_6_2() {
+    let mut camera = Camera { position: Point3::new(0.0, 0.0, 3.0), ..Camera::default() };
+
+    let mut first_mouse = true;
+    let mut last_x: f32 = SCR_WIDTH as f32 / 2.0;
+    let mut last_y: f32 = SCR_HEIGHT as f32 / 2.0;
+
+    // timing
+    let mut delta_time: f32; // time between current frame and last frame
+    let mut last_frame: f32 = 0.0;
+
+    // glfw: initialize and configure
+    // ------------------------------
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+    glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    #[cfg(target_os = "macos")]
+    glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+
+    // glfw window creation
+    // --------------------
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
+
+    window.make_current();
+    window.set_framebuffer_size_polling(true);
+    window.set_cursor_pos_polling(true);
+    window.set_scroll_polling(true);
+
+    // tell GLFW to capture our mouse
+    window.set_cursor_mode(glfw::CursorMode::Disabled);
+
+    // gl: load all OpenGL function pointers
+    // -------------------------------------
+    gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+    let (reflection_shader, refraction_shader, cube, skybox) = unsafe {
+        // configure global opengl state
+        // -----------------------------
+        gl::Enable(gl::DEPTH_TEST);
+
+        // build and compile shaders, the refraction variant of the environment mapping shader is selected with a define
+        // -------------------------------------------------------------------------------------------------------------
+        let reflection_shader = Shader::new(
+            "src/tutorial/_4_advanced_opengl/shaders/6.2.cubemaps.vsh",
+            "src/tutorial/_4_advanced_opengl/shaders/6.2.cubemaps.fsh",
+        );
+        let refraction_shader = Shader::with_defines(
+            "src/tutorial/_4_advanced_opengl/shaders/6.2.cubemaps.vsh",
+            "src/tutorial/_4_advanced_opengl/shaders/6.2.cubemaps.fsh",
+            &[("REFRACTION", "1")],
+        );
+        let skybox_shader = Shader::new(
+            "src/tutorial/_4_advanced_opengl/shaders/6.2.skybox.vsh",
+            "src/tutorial/_4_advanced_opengl/shaders/6.2.skybox.fsh",
+        );
+
+        // set up vertex data (and buffer(s)) and configure vertex attributes
+        // ------------------------------------------------------------------
+        let cube = TutorialGeometry::new_xyzrgb(vec![
+            // positions       // normals
+            -0.5, -0.5, -0.5, 0.0, 0.0, -1.0, 0.5, -0.5, -0.5, 0.0, 0.0, -1.0, 0.5, 0.5, -0.5, 0.0, 0.0, -1.0, //
+            0.5, 0.5, -0.5, 0.0, 0.0, -1.0, -0.5, 0.5, -0.5, 0.0, 0.0, -1.0, -0.5, -0.5, -0.5, 0.0, 0.0, -1.0, //
+            -0.5, -0.5, 0.5, 0.0, 0.0, 1.0, 0.5, -0.5, 0.5, 0.0, 0.0, 1.0, 0.5, 0.5, 0.5, 0.0, 0.0, 1.0, //
+            0.5, 0.5, 0.5, 0.0, 0.0, 1.0, -0.5, 0.5, 0.5, 0.0, 0.0, 1.0, -0.5, -0.5, 0.5, 0.0, 0.0, 1.0, //
+            -0.5, 0.5, 0.5, -1.0, 0.0, 0.0, -0.5, 0.5, -0.5, -1.0, 0.0, 0.0, -0.5, -0.5, -0.5, -1.0, 0.0, 0.0, //
+            -0.5, -0.5, -0.5, -1.0, 0.0, 0.0, -0.5, -0.5, 0.5, -1.0, 0.0, 0.0, -0.5, 0.5, 0.5, -1.0, 0.0, 0.0, //
+            0.5, 0.5, 0.5, 1.0, 0.0, 0.0, 0.5, 0.5, -0.5, 1.0, 0.0, 0.0, 0.5, -0.5, -0.5, 1.0, 0.0, 0.0, //
+            0.5, -0.5, -0.5, 1.0, 0.0, 0.0, 0.5, -0.5, 0.5, 1.0, 0.0, 0.0, 0.5, 0.5, 0.5, 1.0, 0.0, 0.0, //
+            -0.5, -0.5, -0.5, 0.0, -1.0, 0.0, 0.5, -0.5, -0.5, 0.0, -1.0, 0.0, 0.5, -0.5, 0.5, 0.0, -1.0, 0.0, //
+            0.5, -0.5, 0.5, 0.0, -1.0, 0.0, -0.5, -0.5, 0.5, 0.0, -1.0, 0.0, -0.5, -0.5, -0.5, 0.0, -1.0, 0.0, //
+            -0.5, 0.5, -0.5, 0.0, 1.0, 0.0, 0.5, 0.5, -0.5, 0.0, 1.0, 0.0, 0.5, 0.5, 0.5, 0.0, 1.0, 0.0, //
+            0.5, 0.5, 0.5, 0.0, 1.0, 0.0, -0.5, 0.5, 0.5, 0.0, 1.0, 0.0, -0.5, 0.5, -0.5, 0.0, 1.0, 0.0,
+        ]);
+
+        // load textures
+        // -------------
+        // the faces have to be in the order of CUBEMAP_FACES: +X, -X, +Y, -Y, +Z, -Z
+        let faces = [
+            "resources/textures/skybox/right.jpg",
+            "resources/textures/skybox/left.jpg",
+            "resources/textures/skybox/top.jpg",
+            "resources/textures/skybox/bottom.jpg",
+            "resources/textures/skybox/front.jpg",
+            "resources/textures/skybox/back.jpg",
+        ];
+        let skybox = Skybox::from_faces(&faces, skybox_shader);
+
+        // shader configuration
+        // --------------------
+        for shader in &[&reflection_shader, &refraction_shader] {
+            shader.use_program();
+            shader.set_int(c_str!("skybox"), 0);
+        }
+
+        (reflection_shader, refraction_shader, cube, skybox)
+    };
+
+    // 1 = reflection, 2 = refraction
+    let mut refraction = false;
+
+    // render loop
+    // -----------
+    while !window_should_close(&mut glfw, &window) {
+        // per-frame time logic
+        // --------------------
+        let current_frame = get_time(&glfw) as f32;
+        delta_time = current_frame - last_frame;
+        last_frame = current_frame;
+
+        // events
+        // ------
+        process_events(&events, &mut first_mouse, &mut last_x, &mut last_y, &mut camera);
+
+        // input
+        // -----
+        process_input(&mut window, delta_time, &mut camera);
+        if window.get_key(Key::Num1) == Action::Press {
+            refraction = false;
+        }
+        if window.get_key(Key::Num2) == Action::Press {
+            refraction = true;
+        }
+
+        // render
+        // ------
+        unsafe {
+            gl::ClearColor(0.1, 0.1, 0.1, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+
+            // draw scene as normal
+            let shader = if refraction { &refraction_shader } else { &reflection_shader };
+            shader.use_program();
+            let projection: Matrix4<f32> =
+                perspective(Deg(camera.zoom), SCR_WIDTH as f32 / SCR_HEIGHT as f32, 0.1, 100.0);
+            let view = camera.get_view_matrix();
+            shader.set_mat4(c_str!("model"), &Matrix4::identity());
+            shader.set_mat4(c_str!("view"), &view);
+            shader.set_mat4(c_str!("projection"), &projection);
+            shader.set_vector3(c_str!("cameraPos"), &camera.position.to_vec());
+            // cubes
+            gl::ActiveTexture(gl::TEXTURE0);
+            gl::BindTexture(gl::TEXTURE_CUBE_MAP, skybox.texture());
+            cube.draw();
+
+            // draw skybox as last
+            skybox.draw(&view, &projection);
+        }
+
+        // glfw: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
+        // -------------------------------------------------------------------------------
+        window.swap_buffers();
+        glfw.poll_events();
+    }
+}
@@ -0,0 +1,194 @@
+use crate::shared::{
+    create_window, get_time, load_texture, process_events, process_input, window_should_close, Buffer, Camera, Shader,
+};
+use cgmath::{perspective, vec3, Deg, Matrix4, Point3};
+use glfw::{Action, Context, Key};
+use std::ffi::CStr;
+
+use crate::c_str;
+
+// settings
+const SCR_WIDTH: u32 = 1280;
+const SCR_HEIGHT: u32 = 720;
+
+/// quads per side of the water surface
+const GRID_SIZE: usize = 32;
+/// two triangles per quad
+const GRID_VERTICES: usize = GRID_SIZE * GRID_SIZE * 6;
+
+pub fn main_4_7() {
+    let mut camera = Camera { position: Point3::new(0.0, 0.0, 3.0), ..Camera::default() };
+
+    let mut first_mouse = true;
+    let mut last_x: f32 = SCR_WIDTH as f32 / 2.0;
+    let mut last_y: f32 = SCR_HEIGHT as f32 / 2.0;
+
+    // timing
+    let mut delta_time: f32; // time between current frame and last frame
+    let mut last_frame: f32 = 0.0;
+
+    // glfw: initialize and configure
+    // ------------------------------
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+    glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    #[cfg(target_os = "macos")]
+    glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+
+    // glfw window creation
+    // --------------------
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
+
+    window.make_current();
+    window.set_framebuffer_size_polling(true);
+    window.set_cursor_pos_polling(true);
+    window.set_scroll_polling(true);
+
+    // tell GLFW to capture our mouse
+    window.set_cursor_mode(glfw::CursorMode::Disabled);
+
+    // gl: load all OpenGL function pointers
+    // -------------------------------------
+    gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+    let (shader, texture, vaos, mut surface, snapshot) = unsafe {
+        // configure global opengl state
+        // -----------------------------
+        gl::Enable(gl::DEPTH_TEST);
+
+        // build and compile our shader program
+        // ------------------------------------
+        let shader = Shader::new(
+            "src/tutorial/_4_advanced_opengl/shaders/5.1.framebuffers.vsh",
+            "src/tutorial/_4_advanced_opengl/shaders/5.1.framebuffers.fsh",
+        );
+
+        // set up vertex data (and buffer(s)) and configure vertex attributes
+        // ------------------------------------------------------------------
+        // the positions are rewritten every frame, the texture coordinates never change
+        let positions = wave_positions(0.0);
+        let tex_coords = grid_tex_coords();
+
+        let mut vaos = [0, 0];
+        gl::GenVertexArrays(2, vaos.as_mut_ptr());
+
+        // batched instead of interleaved: first all positions, then all texture coordinates (PPP..TT..
+        // instead of PTPT..), so the positions can be updated as one block
+        gl::BindVertexArray(vaos[0]);
+        let surface = Buffer::batched(&[(0, 3, &positions), (1, 2, &tex_coords)], gl::DYNAMIC_DRAW);
+        // a second buffer that receives a copy of the surface when space is pressed
+        gl::BindVertexArray(vaos[1]);
+        let snapshot = Buffer::batched(&[(0, 3, &positions), (1, 2, &tex_coords)], gl::STATIC_DRAW);
+        gl::BindVertexArray(0);
+
+        // load textures
+        // -------------
+        let texture = load_texture("resources/textures/container.jpg");
+
+        // shader configuration
+        // --------------------
+        shader.use_program();
+        shader.set_int(c_str!("texture1"), 0);
+
+        (shader, texture, vaos, surface, snapshot)
+    };
+
+    let mut space_down = false;
+
+    // render loop
+    // -----------
+    while !window_should_close(&mut glfw, &window) {
+        // per-frame time logic
+        // --------------------
+        let current_frame = get_time(&glfw) as f32;
+        delta_time = current_frame - last_frame;
+        last_frame = current_frame;
+
+        // events
+        // ------
+        process_events(&events, &mut first_mouse, &mut last_x, &mut last_y, &mut camera);
+
+        // input
+        // -----
+        process_input(&mut window, delta_time, &mut camera);
+        let space_pressed = window.get_key(Key::Space) == Action::Press;
+
+        // render
+        // ------
+        unsafe {
+            // stream the new positions straight into the mapped buffer; invalidating the range tells
+            // the driver the old contents aren't needed anymore, so it doesn't have to wait for them
+            {
+                let mut mapped =
+                    surface.map_range(0..GRID_VERTICES * 3, gl::MAP_WRITE_BIT | gl::MAP_INVALIDATE_RANGE_BIT);
+                mapped.copy_from_slice(&wave_positions(current_frame));
+            }
+
+            // freeze the current surface into the snapshot buffer, only the positions block is copied
+            if space_pressed && !space_down {
+                surface.copy_to(0, &snapshot, 0, GRID_VERTICES * 3);
+            }
+
+            gl::ClearColor(0.1, 0.1, 0.1, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+
+            shader.use_program();
+            let projection: Matrix4<f32> =
+                perspective(Deg(camera.zoom), SCR_WIDTH as f32 / SCR_HEIGHT as f32, 0.1, 100.0);
+            shader.set_mat4(c_str!("projection"), &projection);
+            shader.set_mat4(c_str!("view"), &camera.get_view_matrix());
+            gl::ActiveTexture(gl::TEXTURE0);
+            gl::BindTexture(gl::TEXTURE_2D, texture);
+
+            // the animated surface on the left, the snapshot on the right
+            for (vao, x) in vaos.iter().zip(&[-1.1, 1.1]) {
+                shader.set_mat4(c_str!("model"), &Matrix4::from_translation(vec3(*x, 0.0, 0.0)));
+                gl::BindVertexArray(*vao);
+                gl::DrawArrays(gl::TRIANGLES, 0, GRID_VERTICES as i32);
+            }
+            gl::BindVertexArray(0);
+        }
+        space_down = space_pressed;
+
+        // glfw: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
+        // -------------------------------------------------------------------------------
+        window.swap_buffers();
+        glfw.poll_events();
+    }
+
+    unsafe {
+        gl::DeleteVertexArrays(2, vaos.as_ptr());
+    }
+}
+
+/// corners of the two triangles of grid cell (x, z), in cell units
+const CELL_CORNERS: [(usize, usize); 6] = [(0, 0), (0, 1), (1, 1), (1, 1), (1, 0), (0, 0)];
+
+/// a 2x2 grid around the origin, displaced by a sine wave moving with `time`
+fn wave_positions(time: f32) -> Vec<f32> {
+    let mut positions = Vec::with_capacity(GRID_VERTICES * 3);
+    for z in 0..GRID_SIZE {
+        for x in 0..GRID_SIZE {
+            for (dx, dz) in &CELL_CORNERS {
+                let px = (x + dx) as f32 / GRID_SIZE as f32 * 2.0 - 1.0;
+                let pz = (z + dz) as f32 / GRID_SIZE as f32 * 2.0 - 1.0;
+                let py = 0.1 * (4.0 * px + 2.0 * time).sin() * (3.0 * pz + time).cos() - 0.5;
+                positions.extend_from_slice(&[px, py, pz]);
+            }
+        }
+    }
+    positions
+}
+
+fn grid_tex_coords() -> Vec<f32> {
+    let mut tex_coords = Vec::with_capacity(GRID_VERTICES * 2);
+    for z in 0..GRID_SIZE {
+        for x in 0..GRID_SIZE {
+            for (dx, dz) in &CELL_CORNERS {
+                tex_coords.push((x + dx) as f32 / GRID_SIZE as f32);
+                tex_coords.push((z + dz) as f32 / GRID_SIZE as f32);
+            }
+        }
+    }
+    tex_coords
+}
@@ -0,0 +1,155 @@
+use crate::shared::{
+    create_window, get_time, process_events, process_input, window_should_close, Camera, Shader, UniformBuffer,
+};
+use cgmath::{perspective, vec3, Deg, Matrix4, Point3};
+use glfw::Context;
+use std::ffi::CStr;
+
+use crate::tutorial::internal::TutorialGeometry;
+use crate::{c_str, glsl_struct};
+
+// settings
+const SCR_WIDTH: u32 = 1280;
+const SCR_HEIGHT: u32 = 720;
+
+glsl_struct! {
+    /// contents of the `Matrices` uniform block in 8.advanced_glsl.vsh
+    struct Matrices {
+        projection: Matrix4<f32>,
+        view: Matrix4<f32>,
+    }
+}
+
+/// byte offset of `view` in the uniform block
+const VIEW_OFFSET: usize = Matrices::STD140_OFFSETS[1].1;
+
+pub fn main_4_8() {
+    let mut camera = Camera { position: Point3::new(0.0, 0.0, 3.0), ..Camera::default() };
+
+    let mut first_mouse = true;
+    let mut last_x: f32 = SCR_WIDTH as f32 / 2.0;
+    let mut last_y: f32 = SCR_HEIGHT as f32 / 2.0;
+
+    // timing
+    let mut delta_time: f32; // time between current frame and last frame
+    let mut last_frame: f32 = 0.0;
+
+    // glfw: initialize and configure
+    // ------------------------------
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+    glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    #[cfg(target_os = "macos")]
+    glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+
+    // glfw window creation
+    // --------------------
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
+
+    window.make_current();
+    window.set_framebuffer_size_polling(true);
+    window.set_cursor_pos_polling(true);
+    window.set_scroll_polling(true);
+
+    // tell GLFW to capture our mouse
+    window.set_cursor_mode(glfw::CursorMode::Disabled);
+
+    // gl: load all OpenGL function pointers
+    // -------------------------------------
+    gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+    let (shaders, cube, matrices) = unsafe {
+        // configure global opengl state
+        // -----------------------------
+        gl::Enable(gl::DEPTH_TEST);
+
+        // build and compile our shader programs, all of them share the vertex shader
+        // --------------------------------------------------------------------------
+        let shaders: Vec<Shader> = ["red", "green", "blue", "yellow"]
+            .iter()
+            .map(|color| {
+                Shader::new(
+                    "src/tutorial/_4_advanced_opengl/shaders/8.advanced_glsl.vsh",
+                    &format!("src/tutorial/_4_advanced_opengl/shaders/8.{}.fsh", color),
+                )
+            })
+            .collect();
+
+        // set up vertex data (and buffer(s)) and configure vertex attributes
+        // ------------------------------------------------------------------
+        let cube = TutorialGeometry::new_xyz(vec![
+            // positions
+            -0.5, -0.5, -0.5, 0.5, -0.5, -0.5, 0.5, 0.5, -0.5, //
+            0.5, 0.5, -0.5, -0.5, 0.5, -0.5, -0.5, -0.5, -0.5, //
+            -0.5, -0.5, 0.5, 0.5, -0.5, 0.5, 0.5, 0.5, 0.5, //
+            0.5, 0.5, 0.5, -0.5, 0.5, 0.5, -0.5, -0.5, 0.5, //
+            -0.5, 0.5, 0.5, -0.5, 0.5, -0.5, -0.5, -0.5, -0.5, //
+            -0.5, -0.5, -0.5, -0.5, -0.5, 0.5, -0.5, 0.5, 0.5, //
+            0.5, 0.5, 0.5, 0.5, 0.5, -0.5, 0.5, -0.5, -0.5, //
+            0.5, -0.5, -0.5, 0.5, -0.5, 0.5, 0.5, 0.5, 0.5, //
+            -0.5, -0.5, -0.5, 0.5, -0.5, -0.5, 0.5, -0.5, 0.5, //
+            0.5, -0.5, 0.5, -0.5, -0.5, 0.5, -0.5, -0.5, -0.5, //
+            -0.5, 0.5, -0.5, 0.5, 0.5, -0.5, 0.5, 0.5, 0.5, //
+            0.5, 0.5, 0.5, -0.5, 0.5, 0.5, -0.5, 0.5, -0.5,
+        ]);
+
+        // configure a uniform buffer object
+        // ---------------------------------
+        // first, create the buffer and link it to a binding point, then connect
+        // the uniform block of each shader to that same binding point
+        let matrices = UniformBuffer::<Matrices>::new();
+        for shader in &shaders {
+            shader.bind_uniform_block("Matrices", &matrices);
+        }
+
+        // store the projection matrix (we only do this once now) (note: we're not using zoom anymore by changing the FoV)
+        let projection: Matrix4<f32> = perspective(Deg(45.0), SCR_WIDTH as f32 / SCR_HEIGHT as f32, 0.1, 100.0);
+        matrices.set(&Matrices { projection, view: camera.get_view_matrix() });
+
+        (shaders, cube, matrices)
+    };
+
+    // each shader draws one cube in its own corner
+    let positions = [vec3(-0.75, 0.75, 0.0), vec3(0.75, 0.75, 0.0), vec3(-0.75, -0.75, 0.0), vec3(0.75, -0.75, 0.0)];
+
+    // render loop
+    // -----------
+    while !window_should_close(&mut glfw, &window) {
+        // per-frame time logic
+        // --------------------
+        let current_frame = get_time(&glfw) as f32;
+        delta_time = current_frame - last_frame;
+        last_frame = current_frame;
+
+        // events
+        // ------
+        process_events(&events, &mut first_mouse, &mut last_x, &mut last_y, &mut camera);
+
+        // input
+        // -----
+        process_input(&mut window, delta_time, &mut camera);
+
+        // render
+        // ------
+        unsafe {
+            gl::ClearColor(0.1, 0.1, 0.1, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+
+            // set the view matrix in the uniform block - we only have to do this once per loop iteration.
+            matrices.set_range(VIEW_OFFSET, &camera.get_view_matrix());
+
+            // draw 4 cubes
+            for (shader, position) in shaders.iter().zip(&positions) {
+                shader.use_program();
+                let model = Matrix4::from_translation(*position);
+                shader.set_mat4(c_str!("model"), &model);
+                cube.draw();
+            }
+        }
+
+        // glfw: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
+        // -------------------------------------------------------------------------------
+        window.swap_buffers();
+        glfw.poll_events();
+    }
+}
@@ -0,0 +1,77 @@
+use crate::shared::{create_window, window_should_close, Shader};
+use glfw::Context;
+
+use crate::tutorial::internal::TutorialGeometry;
+use crate::tutorial::process_events;
+
+// settings
+const SCR_WIDTH: u32 = 1280;
+const SCR_HEIGHT: u32 = 720;
+
+pub fn main_4_9_1() {
+    // glfw: initialize and configure
+    // ------------------------------
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+    glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    #[cfg(target_os = "macos")]
+    glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+
+    // glfw window creation
+    // --------------------
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
+
+    window.make_current();
+    window.set_key_polling(true);
+    window.set_framebuffer_size_polling(true);
+
+    // gl: load all OpenGL function pointers
+    // -------------------------------------
+    gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+    let (shader, points) = unsafe {
+        // build and compile shaders, the geometry shader turns every point into a house
+        // ------------------------------------------------------------------------------
+        let shader = Shader::builder()
+            .vertex("src/tutorial/_4_advanced_opengl/shaders/9.1.geometry_shader.vsh")
+            .geometry("src/tutorial/_4_advanced_opengl/shaders/9.1.geometry_shader.gsh")
+            .fragment("src/tutorial/_4_advanced_opengl/shaders/9.1.geometry_shader.fsh")
+            .build();
+
+        // set up vertex data (and buffer(s)) and configure vertex attributes
+        // ------------------------------------------------------------------
+        let mut points = TutorialGeometry::new_xyrgb(vec![
+            -0.5, 0.5, 1.0, 0.0, 0.0, // top-left
+            0.5, 0.5, 0.0, 1.0, 0.0, // top-right
+            0.5, -0.5, 0.0, 0.0, 1.0, // bottom-right
+            -0.5, -0.5, 1.0, 1.0, 0.0, // bottom-left
+        ]);
+        points.set_primitive(gl::POINTS);
+
+        (shader, points)
+    };
+
+    // render loop
+    // -----------
+    while !window_should_close(&mut glfw, &window) {
+        // events
+        // ------
+        process_events(&mut window, &events);
+
+        // render
+        // ------
+        unsafe {
+            gl::ClearColor(0.1, 0.1, 0.1, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT);
+
+            // draw points
+            shader.use_program();
+            points.draw();
+        }
+
+        // glfw: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
+        // -------------------------------------------------------------------------------
+        window.swap_buffers();
+        glfw.poll_events();
+    }
+}
@@ -0,0 +1,115 @@
+use crate::shared::{
+    create_window, get_time, process_events, process_input, window_should_close, Camera, Model, Shader,
+};
+use cgmath::{perspective, vec3, Deg, Matrix4, Point3};
+use glfw::Context;
+use std::ffi::CStr;
+
+use crate::c_str;
+
+// settings
+const SCR_WIDTH: u32 = 1280;
+const SCR_HEIGHT: u32 = 720;
+
+pub fn main_4_9_2() {
+    let mut camera = Camera { position: Point3::new(0.0, 0.0, 3.0), ..Camera::default() };
+
+    let mut first_mouse = true;
+    let mut last_x = SCR_WIDTH as f32 / 2.0;
+    let mut last_y = SCR_HEIGHT as f32 / 2.0;
+
+    // timing
+    let mut delta_time: f32; // time between current frame and last frame
+    let mut last_frame: f32 = 0.0;
+
+    // glfw: initialize and configure
+    // ------------------------------
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+    glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    #[cfg(target_os = "macos")]
+    glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+
+    // glfw window creation
+    // --------------------
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
+
+    window.make_current();
+    window.set_framebuffer_size_polling(true);
+    window.set_cursor_pos_polling(true);
+    window.set_scroll_polling(true);
+
+    // tell GLFW to capture our mouse
+    window.set_cursor_mode(glfw::CursorMode::Disabled);
+
+    // gl: load all OpenGL function pointers
+    // -------------------------------------
+    gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+    let (shader, nanosuit) = unsafe {
+        // configure global opengl state
+        // -----------------------------
+        gl::Enable(gl::DEPTH_TEST);
+
+        // build and compile shaders
+        // -------------------------
+        let shader = Shader::builder()
+            .vertex("src/tutorial/_4_advanced_opengl/shaders/9.2.geometry_shader.vsh")
+            .geometry("src/tutorial/_4_advanced_opengl/shaders/9.2.geometry_shader.gsh")
+            .fragment("src/tutorial/_4_advanced_opengl/shaders/9.2.geometry_shader.fsh")
+            .build();
+
+        // load models
+        // -----------
+        let nanosuit = Model::new("resources/objects/nanosuit/nanosuit.obj");
+
+        (shader, nanosuit)
+    };
+
+    // render loop
+    // -----------
+    while !window_should_close(&mut glfw, &window) {
+        // per-frame time logic
+        // --------------------
+        let current_frame = get_time(&glfw) as f32;
+        delta_time = current_frame - last_frame;
+        last_frame = current_frame;
+
+        // events
+        // ------
+        process_events(&events, &mut first_mouse, &mut last_x, &mut last_y, &mut camera);
+
+        // input
+        // -----
+        process_input(&mut window, delta_time, &mut camera);
+
+        // render
+        // ------
+        unsafe {
+            gl::ClearColor(0.1, 0.1, 0.1, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+
+            // configure transformation matrices
+            let projection: Matrix4<f32> =
+                perspective(Deg(camera.zoom), SCR_WIDTH as f32 / SCR_HEIGHT as f32, 1.0, 100.0);
+            let view = camera.get_view_matrix();
+            let mut model = Matrix4::<f32>::from_translation(vec3(0.0, -1.75, 0.0));
+            model = model * Matrix4::from_scale(0.2);
+            shader.use_program();
+            shader.set_mat4(c_str!("projection"), &projection);
+            shader.set_mat4(c_str!("view"), &view);
+            shader.set_mat4(c_str!("model"), &model);
+
+            // add time component to geometry shader in the form of a uniform
+            shader.set_float(c_str!("time"), get_time(&glfw) as f32);
+
+            // draw model
+            nanosuit.draw(&shader);
+        }
+
+        // glfw: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
+        // -------------------------------------------------------------------------------
+        window.swap_buffers();
+        glfw.poll_events();
+    }
+}
@@ -0,0 +1,34 @@
+use crate::tutorial::Tutorial;
+
+pub use _1_advanced_lighting::*;
+pub use _2_gamma_correction::*;
+pub use _3_1_shadow_mapping::*;
+pub use _3_2_point_shadows::*;
+pub use _4_normal_mapping::*;
+pub use _5_parallax_mapping::*;
+pub use _6_hdr::*;
+
+pub mod _1_advanced_lighting;
+
+pub mod _2_gamma_correction;
+
+pub mod _3_1_shadow_mapping;
+
+pub mod _3_2_point_shadows;
+
+pub mod _4_normal_mapping;
+
+pub mod _5_parallax_mapping;
+
+pub mod _6_hdr;
+
+/// tutorials of this chapter in reading order
+pub const TUTORIALS: &[Tutorial] = &[
+    Tutorial { id: "5_1", chapter: 5, title: "Advanced Lighting", main: main_5_1 },
+    Tutorial { id: "5_2", chapter: 5, title: "Gamma Correction", main: main_5_2 },
+    Tutorial { id: "5_3_1", chapter: 5, title: "Shadow Mapping", main: main_5_3_1 },
+    Tutorial { id: "5_3_2", chapter: 5, title: "Point Shadows", main: main_5_3_2 },
+    Tutorial { id: "5_4", chapter: 5, title: "Normal Mapping", main: main_5_4 },
+    Tutorial { id: "5_5", chapter: 5, title: "Parallax Mapping", main: main_5_5 },
+    Tutorial { id: "5_6", chapter: 5, title: "HDR", main: main_5_6 },
+];
@@ -0,0 +1,142 @@
+use crate::shared::{
+    create_window, get_time, load_texture, process_events, process_input, window_should_close, Camera, Shader,
+};
+use cgmath::{perspective, vec3, Deg, Matrix4, Point3};
+use glfw::{Action, Context, Key};
+use std::ffi::CStr;
+
+use crate::c_str;
+use crate::tutorial::internal::TutorialGeometry;
+
+// settings
+const SCR_WIDTH: u32 = 1280;
+const SCR_HEIGHT: u32 = 720;
+
+pub fn main_5_1() {
+    let mut camera = Camera { position: Point3::new(0.0, 0.0, 3.0), ..Camera::default() };
+
+    let mut first_mouse = true;
+    let mut last_x: f32 = SCR_WIDTH as f32 / 2.0;
+    let mut last_y: f32 = SCR_HEIGHT as f32 / 2.0;
+
+    // timing
+    let mut delta_time: f32; // time between current frame and last frame
+    let mut last_frame: f32 = 0.0;
+
+    // glfw: initialize and configure
+    // ------------------------------
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+    glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    #[cfg(target_os = "macos")]
+    glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+    // glfw window creation
+    // --------------------
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
+
+    window.make_current();
+    window.set_framebuffer_size_polling(true);
+    window.set_cursor_pos_polling(true);
+    window.set_scroll_polling(true);
+
+    // tell GLFW to capture our mouse
+    window.set_cursor_mode(glfw::CursorMode::Disabled);
+
+    // gl: load all OpenGL function pointers
+    // -------------------------------------
+    gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+    let (shader, plane, floor_texture) = unsafe {
+        // configure global opengl state
+        // -----------------------------
+        gl::Enable(gl::DEPTH_TEST);
+
+        // build and compile shaders
+        // -------------------------
+        let shader = Shader::new(
+            "src/tutorial/_5_advanced_lighting/shaders/1.advanced_lighting.vsh",
+            "src/tutorial/_5_advanced_lighting/shaders/1.advanced_lighting.fsh",
+        );
+
+        // set up vertex data (and buffer(s)) and configure vertex attributes
+        // ------------------------------------------------------------------
+        let plane = TutorialGeometry::new_xyznuv(vec![
+            // positions        // normals     // texcoords
+            10.0, -0.5, 10.0, 0.0, 1.0, 0.0, 10.0, 0.0, -10.0, -0.5, 10.0, 0.0, 1.0, 0.0, 0.0, 0.0, //
+            -10.0, -0.5, -10.0, 0.0, 1.0, 0.0, 0.0, 10.0, //
+            10.0, -0.5, 10.0, 0.0, 1.0, 0.0, 10.0, 0.0, -10.0, -0.5, -10.0, 0.0, 1.0, 0.0, 0.0, 10.0, //
+            10.0, -0.5, -10.0, 0.0, 1.0, 0.0, 10.0, 10.0,
+        ]);
+
+        // load textures
+        // -------------
+        let floor_texture = load_texture("resources/textures/wood.png");
+
+        // shader configuration
+        // --------------------
+        shader.use_program();
+        shader.set_int(c_str!("floorTexture"), 0);
+
+        (shader, plane, floor_texture)
+    };
+
+    // lighting info
+    // -------------
+    let light_pos = vec3(0.0, 0.0, 0.0);
+    let mut blinn = false;
+    let mut blinn_key_pressed = false;
+
+    // render loop
+    // -----------
+    while !window_should_close(&mut glfw, &window) {
+        // per-frame time logic
+        // --------------------
+        let current_frame = get_time(&glfw) as f32;
+        delta_time = current_frame - last_frame;
+        last_frame = current_frame;
+
+        // events
+        // ------
+        process_events(&events, &mut first_mouse, &mut last_x, &mut last_y, &mut camera);
+
+        // input
+        // -----
+        process_input(&mut window, delta_time, &mut camera);
+        // B toggles between Phong and Blinn-Phong
+        if window.get_key(Key::B) == Action::Press && !blinn_key_pressed {
+            blinn = !blinn;
+            blinn_key_pressed = true;
+            println!("{}", if blinn { "Blinn-Phong" } else { "Phong" });
+        }
+        if window.get_key(Key::B) == Action::Release {
+            blinn_key_pressed = false;
+        }
+
+        // render
+        // ------
+        unsafe {
+            gl::ClearColor(0.1, 0.1, 0.1, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+
+            // draw objects
+            shader.use_program();
+            let projection: Matrix4<f32> =
+                perspective(Deg(camera.zoom), SCR_WIDTH as f32 / SCR_HEIGHT as f32, 0.1, 100.0);
+            shader.set_mat4(c_str!("projection"), &projection);
+            shader.set_mat4(c_str!("view"), &camera.get_view_matrix());
+            // set light uniforms
+            shader.set("viewPos", &camera.position);
+            shader.set("lightPos", &light_pos);
+            shader.set_bool(c_str!("blinn"), blinn);
+            // floor
+            gl::ActiveTexture(gl::TEXTURE0);
+            gl::BindTexture(gl::TEXTURE_2D, floor_texture);
+            plane.draw();
+        }
+
+        // glfw: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
+        // -------------------------------------------------------------------------------
+        window.swap_buffers();
+        glfw.poll_events();
+    }
+}
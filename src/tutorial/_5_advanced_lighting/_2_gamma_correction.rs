@@ -0,0 +1,171 @@
+use crate::shared::{
+    create_window, get_time, load_texture, load_texture_srgb, process_events, process_input, window_should_close,
+    Camera, Shader,
+};
+use cgmath::{perspective, vec3, Deg, Matrix4, Point3, Vector3};
+use glfw::{Action, Context, Key};
+use std::ffi::CStr;
+
+use crate::c_str;
+use crate::tutorial::internal::TutorialGeometry;
+
+// settings
+const SCR_WIDTH: u32 = 1280;
+const SCR_HEIGHT: u32 = 720;
+
+/// where the linear lighting result gets converted to sRGB, selected with the number keys
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GammaCorrection {
+    /// lighting on the raw sRGB texture colors, written out as they are
+    Off,
+    /// sRGB texture, `pow(color, 1/2.2)` at the end of the fragment shader
+    Shader,
+    /// sRGB texture, converted by OpenGL on write with `GL_FRAMEBUFFER_SRGB`
+    Framebuffer,
+}
+
+pub fn main_5_2() {
+    let mut camera = Camera { position: Point3::new(0.0, 0.0, 3.0), ..Camera::default() };
+
+    let mut first_mouse = true;
+    let mut last_x: f32 = SCR_WIDTH as f32 / 2.0;
+    let mut last_y: f32 = SCR_HEIGHT as f32 / 2.0;
+
+    // timing
+    let mut delta_time: f32; // time between current frame and last frame
+    let mut last_frame: f32 = 0.0;
+
+    // glfw: initialize and configure
+    // ------------------------------
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+    glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    #[cfg(target_os = "macos")]
+    glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+    // ask for a default framebuffer GL_FRAMEBUFFER_SRGB can convert to sRGB
+    glfw.window_hint(glfw::WindowHint::SRgbCapable(true));
+    // glfw window creation
+    // --------------------
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
+
+    window.make_current();
+    window.set_framebuffer_size_polling(true);
+    window.set_cursor_pos_polling(true);
+    window.set_scroll_polling(true);
+
+    // tell GLFW to capture our mouse
+    window.set_cursor_mode(glfw::CursorMode::Disabled);
+
+    // gl: load all OpenGL function pointers
+    // -------------------------------------
+    gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+    let (shader, plane, floor_texture, floor_texture_gamma_corrected) = unsafe {
+        // configure global opengl state
+        // -----------------------------
+        gl::Enable(gl::DEPTH_TEST);
+
+        // build and compile shaders
+        // -------------------------
+        let shader = Shader::new(
+            "src/tutorial/_5_advanced_lighting/shaders/2.gamma_correction.vsh",
+            "src/tutorial/_5_advanced_lighting/shaders/2.gamma_correction.fsh",
+        );
+
+        // set up vertex data (and buffer(s)) and configure vertex attributes
+        // ------------------------------------------------------------------
+        let plane = TutorialGeometry::new_xyznuv(vec![
+            // positions        // normals     // texcoords
+            10.0, -0.5, 10.0, 0.0, 1.0, 0.0, 10.0, 0.0, -10.0, -0.5, 10.0, 0.0, 1.0, 0.0, 0.0, 0.0, //
+            -10.0, -0.5, -10.0, 0.0, 1.0, 0.0, 0.0, 10.0, //
+            10.0, -0.5, 10.0, 0.0, 1.0, 0.0, 10.0, 0.0, -10.0, -0.5, -10.0, 0.0, 1.0, 0.0, 0.0, 10.0, //
+            10.0, -0.5, -10.0, 0.0, 1.0, 0.0, 10.0, 10.0,
+        ]);
+
+        // load textures, once as they are and once converted from sRGB to linear space when sampled
+        // -----------------------------------------------------------------------------------------
+        let floor_texture = load_texture("resources/textures/wood.png");
+        let floor_texture_gamma_corrected = load_texture_srgb("resources/textures/wood.png");
+
+        // shader configuration
+        // --------------------
+        shader.use_program();
+        shader.set_int(c_str!("floorTexture"), 0);
+
+        (shader, plane, floor_texture, floor_texture_gamma_corrected)
+    };
+
+    // lighting info
+    // -------------
+    let light_positions: [Vector3<f32>; 4] =
+        [vec3(-3.0, 0.0, 0.0), vec3(-1.0, 0.0, 0.0), vec3(1.0, 0.0, 0.0), vec3(3.0, 0.0, 0.0)];
+    let light_colors: [Vector3<f32>; 4] =
+        [vec3(0.25, 0.25, 0.25), vec3(0.50, 0.50, 0.50), vec3(0.75, 0.75, 0.75), vec3(1.00, 1.00, 1.00)];
+
+    let modes = [
+        (Key::Num1, GammaCorrection::Off),
+        (Key::Num2, GammaCorrection::Shader),
+        (Key::Num3, GammaCorrection::Framebuffer),
+    ];
+    let mut mode = GammaCorrection::Off;
+
+    // render loop
+    // -----------
+    while !window_should_close(&mut glfw, &window) {
+        // per-frame time logic
+        // --------------------
+        let current_frame = get_time(&glfw) as f32;
+        delta_time = current_frame - last_frame;
+        last_frame = current_frame;
+
+        // events
+        // ------
+        process_events(&events, &mut first_mouse, &mut last_x, &mut last_y, &mut camera);
+
+        // input
+        // -----
+        process_input(&mut window, delta_time, &mut camera);
+        for (key, key_mode) in &modes {
+            if window.get_key(*key) == Action::Press && mode != *key_mode {
+                mode = *key_mode;
+                println!("gamma correction: {:?}", mode);
+            }
+        }
+
+        // render
+        // ------
+        unsafe {
+            if mode == GammaCorrection::Framebuffer {
+                gl::Enable(gl::FRAMEBUFFER_SRGB);
+            } else {
+                gl::Disable(gl::FRAMEBUFFER_SRGB);
+            }
+
+            gl::ClearColor(0.1, 0.1, 0.1, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+
+            // draw objects
+            shader.use_program();
+            let projection: Matrix4<f32> =
+                perspective(Deg(camera.zoom), SCR_WIDTH as f32 / SCR_HEIGHT as f32, 0.1, 100.0);
+            shader.set_mat4(c_str!("projection"), &projection);
+            shader.set_mat4(c_str!("view"), &camera.get_view_matrix());
+            // set light uniforms
+            shader.set("lightPositions", &light_positions);
+            shader.set("lightColors", &light_colors);
+            shader.set("viewPos", &camera.position);
+            shader.set_bool(c_str!("gamma"), mode != GammaCorrection::Off);
+            shader.set_bool(c_str!("gammaInShader"), mode == GammaCorrection::Shader);
+            // floor
+            gl::ActiveTexture(gl::TEXTURE0);
+            let texture = if mode == GammaCorrection::Off { floor_texture } else { floor_texture_gamma_corrected };
+            gl::BindTexture(gl::TEXTURE_2D, texture);
+            plane.draw();
+        }
+
+        // glfw: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
+        // -------------------------------------------------------------------------------
+        window.swap_buffers();
+        glfw.poll_events();
+    }
+}
@@ -0,0 +1,199 @@
+use crate::shared::{
+    create_window, get_time, load_texture, process_events, process_input, window_should_close, Camera, Shader,
+    ShadowMap, CUBE_VERTICES,
+};
+use cgmath::{perspective, vec3, Deg, InnerSpace, Matrix4, Point3, Rad, SquareMatrix};
+use glfw::{Action, Context, Key};
+use std::ffi::CStr;
+
+use crate::c_str;
+use crate::tutorial::internal::TutorialGeometry;
+
+// settings
+const SCR_WIDTH: u32 = 1280;
+const SCR_HEIGHT: u32 = 720;
+const SHADOW_SIZE: i32 = 1024;
+
+pub fn main_5_3_1() {
+    let mut camera = Camera { position: Point3::new(0.0, 1.0, 4.0), ..Camera::default() };
+
+    let mut first_mouse = true;
+    let mut last_x: f32 = SCR_WIDTH as f32 / 2.0;
+    let mut last_y: f32 = SCR_HEIGHT as f32 / 2.0;
+
+    // timing
+    let mut delta_time: f32; // time between current frame and last frame
+    let mut last_frame: f32 = 0.0;
+
+    // glfw: initialize and configure
+    // ------------------------------
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+    glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    #[cfg(target_os = "macos")]
+    glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+    // glfw window creation
+    // --------------------
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
+
+    window.make_current();
+    window.set_framebuffer_size_polling(true);
+    window.set_cursor_pos_polling(true);
+    window.set_scroll_polling(true);
+
+    // tell GLFW to capture our mouse
+    window.set_cursor_mode(glfw::CursorMode::Disabled);
+
+    // gl: load all OpenGL function pointers
+    // -------------------------------------
+    gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+    let (shader, debug_shader, plane, cube, debug_quad, wood_texture, mut shadow_map) = unsafe {
+        // configure global opengl state
+        // -----------------------------
+        gl::Enable(gl::DEPTH_TEST);
+
+        // build and compile shaders
+        // -------------------------
+        let shader = Shader::new(
+            "src/tutorial/_5_advanced_lighting/shaders/3.1.shadow_mapping.vsh",
+            "src/tutorial/_5_advanced_lighting/shaders/3.1.shadow_mapping.fsh",
+        );
+        let debug_shader = Shader::new(
+            "src/tutorial/_5_advanced_lighting/shaders/3.1.debug_quad.vsh",
+            "src/tutorial/_5_advanced_lighting/shaders/3.1.debug_quad.fsh",
+        );
+
+        // set up vertex data (and buffer(s)) and configure vertex attributes
+        // ------------------------------------------------------------------
+        let plane = TutorialGeometry::new_xyznuv(vec![
+            // positions        // normals     // texcoords
+            25.0, -0.5, 25.0, 0.0, 1.0, 0.0, 25.0, 0.0, -25.0, -0.5, 25.0, 0.0, 1.0, 0.0, 0.0, 0.0, //
+            -25.0, -0.5, -25.0, 0.0, 1.0, 0.0, 0.0, 25.0, //
+            25.0, -0.5, 25.0, 0.0, 1.0, 0.0, 25.0, 0.0, -25.0, -0.5, -25.0, 0.0, 1.0, 0.0, 0.0, 25.0, //
+            25.0, -0.5, -25.0, 0.0, 1.0, 0.0, 25.0, 25.0,
+        ]);
+        let cube = TutorialGeometry::new_xyznuv(CUBE_VERTICES.to_vec());
+        // the depth map in the lower left corner of the screen
+        let debug_quad = TutorialGeometry::new_xyuv(vec![
+            // positions // texCoords
+            -1.0, -0.4, 0.0, 1.0, -1.0, -1.0, 0.0, 0.0, -0.4, -1.0, 1.0, 0.0, //
+            -1.0, -0.4, 0.0, 1.0, -0.4, -1.0, 1.0, 0.0, -0.4, -0.4, 1.0, 1.0,
+        ]);
+
+        // load textures
+        // -------------
+        let wood_texture = load_texture("resources/textures/wood.png");
+
+        // configure depth map
+        // -------------------
+        let shadow_map = ShadowMap::new(SHADOW_SIZE).unwrap_or_else(|e| panic!("{}", e));
+
+        // shader configuration
+        // --------------------
+        shader.use_program();
+        shader.set_int(c_str!("diffuseTexture"), 0);
+        debug_shader.use_program();
+        debug_shader.set_int(c_str!("depthMap"), 0);
+
+        (shader, debug_shader, plane, cube, debug_quad, wood_texture, shadow_map)
+    };
+
+    // lighting info
+    // -------------
+    let light_pos = Point3::new(-2.0, 4.0, -1.0);
+    shadow_map.look_at(light_pos, Point3::new(0.0, 0.0, 0.0), 10.0, 1.0, 7.5);
+
+    // the scene, drawn once from the light for the depth map and once from the camera
+    let render_scene = |shader: &Shader| unsafe {
+        // floor
+        shader.set_mat4(c_str!("model"), &Matrix4::identity());
+        plane.draw();
+        // cubes
+        let cubes = [
+            Matrix4::from_translation(vec3(0.0, 1.5, 0.0)),
+            Matrix4::from_translation(vec3(2.0, 0.0, 1.0)),
+            Matrix4::from_translation(vec3(-1.0, 0.0, 2.0))
+                * Matrix4::from_axis_angle(vec3(1.0, 0.0, 1.0).normalize(), Rad::from(Deg(60.0)))
+                * Matrix4::from_scale(0.5),
+        ];
+        for model in &cubes {
+            shader.set_mat4(c_str!("model"), model);
+            cube.draw();
+        }
+    };
+
+    let pcf_modes = [(Key::Num1, 0), (Key::Num2, 1), (Key::Num3, 2)];
+
+    // render loop
+    // -----------
+    while !window_should_close(&mut glfw, &window) {
+        // per-frame time logic
+        // --------------------
+        let current_frame = get_time(&glfw) as f32;
+        delta_time = current_frame - last_frame;
+        last_frame = current_frame;
+
+        // events
+        // ------
+        process_events(&events, &mut first_mouse, &mut last_x, &mut last_y, &mut camera);
+
+        // input
+        // -----
+        process_input(&mut window, delta_time, &mut camera);
+        // Up and Down scale the bias against shadow acne, the number keys select the PCF radius
+        if window.get_key(Key::Up) == Action::Press {
+            shadow_map.min_bias *= 1.0 + delta_time;
+            shadow_map.max_bias *= 1.0 + delta_time;
+        }
+        if window.get_key(Key::Down) == Action::Press {
+            shadow_map.min_bias /= 1.0 + delta_time;
+            shadow_map.max_bias /= 1.0 + delta_time;
+        }
+        for (key, radius) in &pcf_modes {
+            if window.get_key(*key) == Action::Press && shadow_map.pcf_radius != *radius {
+                shadow_map.pcf_radius = *radius;
+                println!("PCF radius: {}", radius);
+            }
+        }
+
+        // render
+        // ------
+        unsafe {
+            gl::ClearColor(0.1, 0.1, 0.1, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+
+            // 1. render depth of scene to texture (from light's perspective)
+            // --------------------------------------------------------------
+            render_scene(shadow_map.begin());
+            let (width, height) = window.get_framebuffer_size();
+            shadow_map.end(width, height);
+
+            // 2. render scene as normal using the generated depth/shadow map
+            // --------------------------------------------------------------
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+            shadow_map.apply(&shader, 1);
+            gl::BindTexture(gl::TEXTURE_2D, wood_texture);
+            let projection: Matrix4<f32> = perspective(Deg(camera.zoom), width as f32 / height as f32, 0.1, 100.0);
+            shader.set_mat4(c_str!("projection"), &projection);
+            shader.set_mat4(c_str!("view"), &camera.get_view_matrix());
+            // set light uniforms
+            shader.set("viewPos", &camera.position);
+            shader.set("lightPos", &light_pos);
+            render_scene(&shader);
+
+            // render depth map to quad for visual debugging, while Space is held
+            // ------------------------------------------------------------------
+            if window.get_key(Key::Space) == Action::Press {
+                debug_shader.use_program();
+                gl::BindTexture(gl::TEXTURE_2D, shadow_map.depth_texture());
+                debug_quad.draw();
+            }
+        }
+
+        // glfw: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
+        // -------------------------------------------------------------------------------
+        window.swap_buffers();
+        glfw.poll_events();
+    }
+}
@@ -0,0 +1,180 @@
+use crate::shared::{
+    create_window, get_time, load_texture, process_events, process_input, window_should_close, Camera, PointShadow,
+    Shader, CUBE_VERTICES,
+};
+use cgmath::{perspective, vec3, Deg, InnerSpace, Matrix4, Point3, Rad};
+use glfw::{Action, Context, Key};
+use std::ffi::CStr;
+
+use crate::c_str;
+use crate::tutorial::internal::TutorialGeometry;
+
+// settings
+const SCR_WIDTH: u32 = 1280;
+const SCR_HEIGHT: u32 = 720;
+const SHADOW_SIZE: i32 = 1024;
+
+/// how shadows are rendered, selected with the number keys
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Shadows {
+    Off,
+    /// a single lookup in the depth cubemap
+    Hard,
+    /// percentage-closer filtering over a disk of offsets
+    Soft,
+}
+
+pub fn main_5_3_2() {
+    let mut camera = Camera { position: Point3::new(0.0, 0.0, 3.0), ..Camera::default() };
+
+    let mut first_mouse = true;
+    let mut last_x: f32 = SCR_WIDTH as f32 / 2.0;
+    let mut last_y: f32 = SCR_HEIGHT as f32 / 2.0;
+
+    // timing
+    let mut delta_time: f32; // time between current frame and last frame
+    let mut last_frame: f32 = 0.0;
+
+    // glfw: initialize and configure
+    // ------------------------------
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+    glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    #[cfg(target_os = "macos")]
+    glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+    // glfw window creation
+    // --------------------
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
+
+    window.make_current();
+    window.set_framebuffer_size_polling(true);
+    window.set_cursor_pos_polling(true);
+    window.set_scroll_polling(true);
+
+    // tell GLFW to capture our mouse
+    window.set_cursor_mode(glfw::CursorMode::Disabled);
+
+    // gl: load all OpenGL function pointers
+    // -------------------------------------
+    gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+    let (shader, cube, wood_texture, mut point_shadow) = unsafe {
+        // configure global opengl state
+        // -----------------------------
+        gl::Enable(gl::DEPTH_TEST);
+
+        // build and compile shaders
+        // -------------------------
+        let shader = Shader::new(
+            "src/tutorial/_5_advanced_lighting/shaders/3.2.point_shadows.vsh",
+            "src/tutorial/_5_advanced_lighting/shaders/3.2.point_shadows.fsh",
+        );
+
+        // set up vertex data (and buffer(s)) and configure vertex attributes
+        // ------------------------------------------------------------------
+        let cube = TutorialGeometry::new_xyznuv(CUBE_VERTICES.to_vec());
+
+        // load textures
+        // -------------
+        let wood_texture = load_texture("resources/textures/wood.png");
+
+        // configure depth cubemap
+        // -----------------------
+        let point_shadow = PointShadow::new(SHADOW_SIZE, 1.0, 25.0).unwrap_or_else(|e| panic!("{}", e));
+
+        // shader configuration
+        // --------------------
+        shader.use_program();
+        shader.set_int(c_str!("diffuseTexture"), 0);
+
+        (shader, cube, wood_texture, point_shadow)
+    };
+
+    // the scene, drawn once from the light for the depth cubemap and once from the camera
+    let render_scene = |shader: &Shader| unsafe {
+        // room cube
+        shader.set_mat4(c_str!("model"), &Matrix4::from_scale(10.0));
+        // A small little hack to invert normals when drawing cube from the inside so lighting still works.
+        shader.try_set("reverseNormals", &true);
+        cube.draw();
+        shader.try_set("reverseNormals", &false);
+        // cubes
+        let cubes = [
+            Matrix4::from_translation(vec3(4.0, -3.5, 0.0)),
+            Matrix4::from_translation(vec3(2.0, 3.0, 1.0)) * Matrix4::from_scale(1.5),
+            Matrix4::from_translation(vec3(-3.0, -1.0, 0.0)),
+            Matrix4::from_translation(vec3(-1.5, 1.0, 1.5)),
+            Matrix4::from_translation(vec3(-1.5, 2.0, -3.0))
+                * Matrix4::from_axis_angle(vec3(1.0, 0.0, 1.0).normalize(), Rad::from(Deg(60.0)))
+                * Matrix4::from_scale(1.5),
+        ];
+        for model in &cubes {
+            shader.set_mat4(c_str!("model"), model);
+            cube.draw();
+        }
+    };
+
+    let modes = [(Key::Num1, Shadows::Off), (Key::Num2, Shadows::Hard), (Key::Num3, Shadows::Soft)];
+    let mut mode = Shadows::Soft;
+
+    // render loop
+    // -----------
+    while !window_should_close(&mut glfw, &window) {
+        // per-frame time logic
+        // --------------------
+        let current_frame = get_time(&glfw) as f32;
+        delta_time = current_frame - last_frame;
+        last_frame = current_frame;
+
+        // events
+        // ------
+        process_events(&events, &mut first_mouse, &mut last_x, &mut last_y, &mut camera);
+
+        // input
+        // -----
+        process_input(&mut window, delta_time, &mut camera);
+        for (key, key_mode) in &modes {
+            if window.get_key(*key) == Action::Press && mode != *key_mode {
+                mode = *key_mode;
+                point_shadow.pcf = mode == Shadows::Soft;
+                println!("shadows: {:?}", mode);
+            }
+        }
+
+        // move light position over time
+        let light_pos = Point3::new(0.0, 0.0, (get_time(&glfw) as f32 * 0.5).sin() * 3.0);
+        point_shadow.set_light_position(light_pos);
+
+        // render
+        // ------
+        unsafe {
+            gl::ClearColor(0.1, 0.1, 0.1, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+
+            // 1. render scene to depth cubemap
+            // --------------------------------
+            render_scene(point_shadow.begin());
+            let (width, height) = window.get_framebuffer_size();
+            point_shadow.end(width, height);
+
+            // 2. render scene as normal
+            // -------------------------
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+            point_shadow.apply(&shader, 1);
+            gl::BindTexture(gl::TEXTURE_2D, wood_texture);
+            let projection: Matrix4<f32> = perspective(Deg(camera.zoom), width as f32 / height as f32, 0.1, 100.0);
+            shader.set_mat4(c_str!("projection"), &projection);
+            shader.set_mat4(c_str!("view"), &camera.get_view_matrix());
+            // set lighting uniforms
+            shader.set("lightPos", &light_pos);
+            shader.set("viewPos", &camera.position);
+            shader.set_bool(c_str!("shadows"), mode != Shadows::Off);
+            render_scene(&shader);
+        }
+
+        // glfw: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
+        // -------------------------------------------------------------------------------
+        window.swap_buffers();
+        glfw.poll_events();
+    }
+}
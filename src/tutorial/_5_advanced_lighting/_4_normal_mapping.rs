@@ -0,0 +1,157 @@
+use crate::shared::{
+    compute_tangents, create_window, get_time, load_texture, process_events, process_input, window_should_close,
+    Camera, Mesh, Shader, Texture, Vertex,
+};
+use cgmath::{perspective, vec2, vec3, Deg, InnerSpace, Matrix4, Point3, Vector3};
+use glfw::{Action, Context, Key};
+use std::ffi::CStr;
+
+use crate::c_str;
+
+// settings
+const SCR_WIDTH: u32 = 1280;
+const SCR_HEIGHT: u32 = 720;
+
+/// A 2x2 quad in the XY plane facing +Z, with the tangent space generated
+/// from its texture coordinates by `compute_tangents`
+pub fn tangent_space_quad(textures: Vec<Texture>) -> Mesh {
+    // positions and texture coordinates of the corners
+    let corners = [
+        (vec3(-1.0, 1.0, 0.0), vec2(0.0, 1.0)),
+        (vec3(-1.0, -1.0, 0.0), vec2(0.0, 0.0)),
+        (vec3(1.0, -1.0, 0.0), vec2(1.0, 0.0)),
+        (vec3(1.0, 1.0, 0.0), vec2(1.0, 1.0)),
+    ];
+    let mut vertices: Vec<Vertex> = corners
+        .iter()
+        .map(|&(position, tex_coords)| Vertex {
+            position,
+            normal: vec3(0.0, 0.0, 1.0),
+            tex_coords,
+            ..Vertex::default()
+        })
+        .collect();
+    let indices = vec![0, 1, 2, 0, 2, 3];
+    compute_tangents(&mut vertices, &indices);
+    Mesh::new(vertices, indices, textures)
+}
+
+/// loads a texture for `Mesh`, `type_` being e.g. "texture_diffuse"
+pub unsafe fn load_mesh_texture(path: &str, type_: &str) -> Texture {
+    Texture { id: load_texture(path), type_: type_.into(), path: path.into() }
+}
+
+pub fn main_5_4() {
+    let mut camera = Camera { position: Point3::new(0.0, 0.0, 3.0), ..Camera::default() };
+
+    let mut first_mouse = true;
+    let mut last_x: f32 = SCR_WIDTH as f32 / 2.0;
+    let mut last_y: f32 = SCR_HEIGHT as f32 / 2.0;
+
+    // timing
+    let mut delta_time: f32; // time between current frame and last frame
+    let mut last_frame: f32 = 0.0;
+
+    // glfw: initialize and configure
+    // ------------------------------
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+    glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    #[cfg(target_os = "macos")]
+    glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+    // glfw window creation
+    // --------------------
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
+
+    window.make_current();
+    window.set_framebuffer_size_polling(true);
+    window.set_cursor_pos_polling(true);
+    window.set_scroll_polling(true);
+
+    // tell GLFW to capture our mouse
+    window.set_cursor_mode(glfw::CursorMode::Disabled);
+
+    // gl: load all OpenGL function pointers
+    // -------------------------------------
+    gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+    let (shader, quad) = unsafe {
+        // configure global opengl state
+        // -----------------------------
+        gl::Enable(gl::DEPTH_TEST);
+
+        // build and compile shaders
+        // -------------------------
+        let shader = Shader::new(
+            "src/tutorial/_5_advanced_lighting/shaders/4.normal_mapping.vsh",
+            "src/tutorial/_5_advanced_lighting/shaders/4.normal_mapping.fsh",
+        );
+
+        // load textures and set up the quad
+        // ---------------------------------
+        let quad = tangent_space_quad(vec![
+            load_mesh_texture("resources/textures/brickwall.jpg", "texture_diffuse"),
+            load_mesh_texture("resources/textures/brickwall_normal.jpg", "texture_normal"),
+        ]);
+
+        (shader, quad)
+    };
+
+    // lighting info
+    // -------------
+    let light_pos = vec3(0.5, 1.0, 0.3);
+
+    let modes = [(Key::Num1, false), (Key::Num2, true)];
+    let mut normal_mapping = true;
+
+    // render loop
+    // -----------
+    while !window_should_close(&mut glfw, &window) {
+        // per-frame time logic
+        // --------------------
+        let current_frame = get_time(&glfw) as f32;
+        delta_time = current_frame - last_frame;
+        last_frame = current_frame;
+
+        // events
+        // ------
+        process_events(&events, &mut first_mouse, &mut last_x, &mut last_y, &mut camera);
+
+        // input
+        // -----
+        process_input(&mut window, delta_time, &mut camera);
+        for (key, enabled) in &modes {
+            if window.get_key(*key) == Action::Press && normal_mapping != *enabled {
+                normal_mapping = *enabled;
+                println!("normal mapping: {}", if normal_mapping { "on" } else { "off" });
+            }
+        }
+
+        // render
+        // ------
+        unsafe {
+            gl::ClearColor(0.1, 0.1, 0.1, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+
+            // configure view/projection matrices
+            shader.use_program();
+            let projection: Matrix4<f32> =
+                perspective(Deg(camera.zoom), SCR_WIDTH as f32 / SCR_HEIGHT as f32, 0.1, 100.0);
+            shader.set_mat4(c_str!("projection"), &projection);
+            shader.set_mat4(c_str!("view"), &camera.get_view_matrix());
+            // render normal-mapped quad, rotating it to show the lighting follows the bricks from any angle
+            let axis: Vector3<f32> = vec3(1.0, 0.0, 1.0).normalize();
+            let model = Matrix4::from_axis_angle(axis, Deg(get_time(&glfw) as f32 * -10.0));
+            shader.set_mat4(c_str!("model"), &model);
+            shader.set("viewPos", &camera.position);
+            shader.set("lightPos", &light_pos);
+            shader.set_bool(c_str!("normalMapping"), normal_mapping);
+            quad.draw(&shader);
+        }
+
+        // glfw: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
+        // -------------------------------------------------------------------------------
+        window.swap_buffers();
+        glfw.poll_events();
+    }
+}
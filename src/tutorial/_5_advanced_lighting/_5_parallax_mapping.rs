@@ -0,0 +1,206 @@
+use crate::shared::{
+    create_window, get_time, process_events, process_input, window_should_close, Camera, Shader, Texture,
+};
+use crate::tutorial::_5_advanced_lighting::{load_mesh_texture, tangent_space_quad};
+use cgmath::{perspective, vec3, Deg, InnerSpace, Matrix4, Point3, Vector3};
+use glfw::{Action, Context, Key};
+use std::ffi::CStr;
+use std::os::raw::c_void;
+
+use crate::c_str;
+
+// settings
+const SCR_WIDTH: u32 = 1280;
+const SCR_HEIGHT: u32 = 720;
+
+/// the shader variants, see 5.parallax_mapping.fsh
+const VARIANTS: [(&str, Key); 4] = [
+    ("PARALLAX_NONE", Key::Num1),
+    ("PARALLAX_PLAIN", Key::Num2),
+    ("PARALLAX_STEEP", Key::Num3),
+    ("PARALLAX_OCCLUSION", Key::Num4),
+];
+
+/// Loads the image at `path` as a depth map for `Mesh`, with the darkest
+/// parts the deepest. There's no displacement map for the bricks in the
+/// resources, but the mortar is the darkest part of brickwall.jpg.
+pub unsafe fn load_depth_from_luminance(path: &str) -> Texture {
+    let img = image::open(path).expect("Texture failed to load").to_luma8();
+    let (darkest, brightest) = img.pixels().fold((255, 0), |(min, max), p| (p[0].min(min), p[0].max(max)));
+    let range = f32::from(brightest.saturating_sub(darkest).max(1));
+    let data: Vec<u8> = img
+        .pixels()
+        .map(|p| {
+            let depth = 1.0 - f32::from(p[0] - darkest) / range;
+            // squared to keep the faces of the bricks mostly flat
+            (depth * depth * 255.0).round() as u8
+        })
+        .collect();
+
+    let mut texture_id = 0;
+    gl::GenTextures(1, &mut texture_id);
+    gl::BindTexture(gl::TEXTURE_2D, texture_id);
+    // rows of single bytes aren't 4-byte aligned for every width
+    gl::PixelStorei(gl::UNPACK_ALIGNMENT, 1);
+    gl::TexImage2D(
+        gl::TEXTURE_2D,
+        0,
+        gl::R8 as i32,
+        img.width() as i32,
+        img.height() as i32,
+        0,
+        gl::RED,
+        gl::UNSIGNED_BYTE,
+        &data[0] as *const u8 as *const c_void,
+    );
+    gl::PixelStorei(gl::UNPACK_ALIGNMENT, 4);
+    gl::GenerateMipmap(gl::TEXTURE_2D);
+
+    gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::REPEAT as i32);
+    gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::REPEAT as i32);
+    gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR_MIPMAP_LINEAR as i32);
+    gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+
+    Texture { id: texture_id, type_: "texture_height".into(), path: path.into() }
+}
+
+pub fn main_5_5() {
+    let mut camera = Camera { position: Point3::new(0.0, 0.0, 3.0), ..Camera::default() };
+
+    let mut first_mouse = true;
+    let mut last_x: f32 = SCR_WIDTH as f32 / 2.0;
+    let mut last_y: f32 = SCR_HEIGHT as f32 / 2.0;
+
+    // timing
+    let mut delta_time: f32; // time between current frame and last frame
+    let mut last_frame: f32 = 0.0;
+
+    // glfw: initialize and configure
+    // ------------------------------
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+    glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    #[cfg(target_os = "macos")]
+    glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+    // glfw window creation
+    // --------------------
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
+
+    window.make_current();
+    window.set_framebuffer_size_polling(true);
+    window.set_cursor_pos_polling(true);
+    window.set_scroll_polling(true);
+
+    // tell GLFW to capture our mouse
+    window.set_cursor_mode(glfw::CursorMode::Disabled);
+
+    // gl: load all OpenGL function pointers
+    // -------------------------------------
+    gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+    let (shaders, quad) = unsafe {
+        // configure global opengl state
+        // -----------------------------
+        gl::Enable(gl::DEPTH_TEST);
+
+        // build and compile shaders, one per variant
+        // ------------------------------------------
+        let shaders: Vec<Shader> = VARIANTS
+            .iter()
+            .map(|(variant, _)| {
+                Shader::with_defines(
+                    "src/tutorial/_5_advanced_lighting/shaders/4.normal_mapping.vsh",
+                    "src/tutorial/_5_advanced_lighting/shaders/5.parallax_mapping.fsh",
+                    &[("PARALLAX", variant)],
+                )
+            })
+            .collect();
+
+        // load textures and set up the quad
+        // ---------------------------------
+        let quad = tangent_space_quad(vec![
+            load_mesh_texture("resources/textures/brickwall.jpg", "texture_diffuse"),
+            load_mesh_texture("resources/textures/brickwall_normal.jpg", "texture_normal"),
+            load_depth_from_luminance("resources/textures/brickwall.jpg"),
+        ]);
+
+        (shaders, quad)
+    };
+
+    // lighting info
+    // -------------
+    let light_pos = vec3(0.5, 1.0, 0.3);
+
+    let mut variant = VARIANTS.len() - 1;
+    let mut height_scale: f32 = 0.1;
+    // steep and occlusion mapping use min_layers to max_layers, depending on the view angle
+    let mut max_layers: f32 = 32.0;
+
+    // render loop
+    // -----------
+    while !window_should_close(&mut glfw, &window) {
+        // per-frame time logic
+        // --------------------
+        let current_frame = get_time(&glfw) as f32;
+        delta_time = current_frame - last_frame;
+        last_frame = current_frame;
+
+        // events
+        // ------
+        process_events(&events, &mut first_mouse, &mut last_x, &mut last_y, &mut camera);
+
+        // input
+        // -----
+        process_input(&mut window, delta_time, &mut camera);
+        for (i, (name, key)) in VARIANTS.iter().enumerate() {
+            if window.get_key(*key) == Action::Press && variant != i {
+                variant = i;
+                println!("parallax mapping: {}", name);
+            }
+        }
+        // Up and Down scale the depth of the bricks, Right and Left the number of layers
+        if window.get_key(Key::Up) == Action::Press {
+            height_scale *= 1.0 + delta_time;
+        }
+        if window.get_key(Key::Down) == Action::Press {
+            height_scale /= 1.0 + delta_time;
+        }
+        if window.get_key(Key::Right) == Action::Press {
+            max_layers = (max_layers * (1.0 + delta_time)).min(256.0);
+        }
+        if window.get_key(Key::Left) == Action::Press {
+            max_layers = (max_layers / (1.0 + delta_time)).max(4.0);
+        }
+
+        // render
+        // ------
+        unsafe {
+            gl::ClearColor(0.1, 0.1, 0.1, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+
+            // configure view/projection matrices
+            let shader = &shaders[variant];
+            shader.use_program();
+            let projection: Matrix4<f32> =
+                perspective(Deg(camera.zoom), SCR_WIDTH as f32 / SCR_HEIGHT as f32, 0.1, 100.0);
+            shader.set_mat4(c_str!("projection"), &projection);
+            shader.set_mat4(c_str!("view"), &camera.get_view_matrix());
+            // render parallax-mapped quad
+            let axis: Vector3<f32> = vec3(1.0, 0.0, 1.0).normalize();
+            let model = Matrix4::from_axis_angle(axis, Deg(get_time(&glfw) as f32 * -10.0));
+            shader.set_mat4(c_str!("model"), &model);
+            shader.set("viewPos", &camera.position);
+            shader.set("lightPos", &light_pos);
+            // only some variants use these
+            shader.try_set("heightScale", &height_scale);
+            shader.try_set("minLayers", &(max_layers / 4.0).round());
+            shader.try_set("maxLayers", &max_layers.round());
+            quad.draw(shader);
+        }
+
+        // glfw: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
+        // -------------------------------------------------------------------------------
+        window.swap_buffers();
+        glfw.poll_events();
+    }
+}
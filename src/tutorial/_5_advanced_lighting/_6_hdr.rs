@@ -0,0 +1,178 @@
+use crate::shared::{
+    create_window, get_time, load_texture_srgb, process_events_with, process_input, window_should_close, Camera,
+    HdrTarget, Shader, ToneMapping, CUBE_VERTICES,
+};
+use cgmath::{perspective, vec3, Deg, Matrix4, Point3};
+use glfw::{Action, Context, Key};
+use std::ffi::CStr;
+
+use crate::c_str;
+use crate::tutorial::internal::TutorialGeometry;
+
+// settings
+const SCR_WIDTH: u32 = 1280;
+const SCR_HEIGHT: u32 = 720;
+
+pub fn main_5_6() {
+    let mut camera = Camera { position: Point3::new(0.0, 0.0, -5.0), ..Camera::default() };
+
+    let mut first_mouse = true;
+    let mut last_x: f32 = SCR_WIDTH as f32 / 2.0;
+    let mut last_y: f32 = SCR_HEIGHT as f32 / 2.0;
+
+    // timing
+    let mut delta_time: f32; // time between current frame and last frame
+    let mut last_frame: f32 = 0.0;
+
+    // glfw: initialize and configure
+    // ------------------------------
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+    glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+    #[cfg(target_os = "macos")]
+    glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
+    // glfw window creation
+    // --------------------
+    let (mut window, events) = create_window(&mut glfw, SCR_WIDTH, SCR_HEIGHT, "LearnOpenGL");
+
+    window.make_current();
+    window.set_framebuffer_size_polling(true);
+    window.set_cursor_pos_polling(true);
+    window.set_scroll_polling(true);
+
+    // tell GLFW to capture our mouse
+    window.set_cursor_mode(glfw::CursorMode::Disabled);
+
+    // gl: load all OpenGL function pointers
+    // -------------------------------------
+    gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+    let (shader, cube, wood_texture, mut hdr) = unsafe {
+        // configure global opengl state
+        // -----------------------------
+        gl::Enable(gl::DEPTH_TEST);
+
+        // build and compile shaders
+        // -------------------------
+        let shader = Shader::new(
+            "src/tutorial/_5_advanced_lighting/shaders/6.lighting.vsh",
+            "src/tutorial/_5_advanced_lighting/shaders/6.lighting.fsh",
+        );
+
+        // set up vertex data (and buffer(s)) and configure vertex attributes
+        // ------------------------------------------------------------------
+        let cube = TutorialGeometry::new_xyznuv(CUBE_VERTICES.to_vec());
+
+        // load textures
+        // -------------
+        // the tone mapping pass applies gamma correction, so the texture is converted to linear space
+        let wood_texture = load_texture_srgb("resources/textures/wood.png");
+
+        // configure floating point framebuffer
+        // ------------------------------------
+        let (width, height) = window.get_framebuffer_size();
+        let hdr = HdrTarget::new(width, height).unwrap_or_else(|e| panic!("{}", e));
+
+        // shader configuration
+        // --------------------
+        shader.use_program();
+        shader.set_int(c_str!("diffuseTexture"), 0);
+
+        (shader, cube, wood_texture, hdr)
+    };
+
+    // lighting info
+    // -------------
+    // positions, the tunnel runs along -Z so the camera looks down it from the start
+    let light_positions = [
+        vec3(0.0, 0.0, -49.5), // back light
+        vec3(-1.4, -1.9, -9.0),
+        vec3(0.0, -1.8, -4.0),
+        vec3(0.8, -1.7, -6.0),
+    ];
+    // colors: the light at the end of the tunnel is 200 times brighter than the window can show
+    let light_colors = [vec3(200.0, 200.0, 200.0), vec3(0.1, 0.0, 0.0), vec3(0.0, 0.0, 0.2), vec3(0.0, 0.1, 0.0)];
+
+    let modes = [
+        (Key::Num1, ToneMapping::None),
+        (Key::Num2, ToneMapping::Reinhard),
+        (Key::Num3, ToneMapping::Exposure),
+        (Key::Num4, ToneMapping::Aces),
+    ];
+    let mut auto_exposure_key_pressed = false;
+    println!("1-4: tone mapping {:?}, Q/E: exposure, Space: auto exposure", ToneMapping::ALL);
+
+    // render loop
+    // -----------
+    while !window_should_close(&mut glfw, &window) {
+        // per-frame time logic
+        // --------------------
+        let current_frame = get_time(&glfw) as f32;
+        delta_time = current_frame - last_frame;
+        last_frame = current_frame;
+
+        // events
+        // ------
+        process_events_with(&events, &mut first_mouse, &mut last_x, &mut last_y, &mut camera, |event| unsafe {
+            // keep the floating point framebuffer the same size as the window
+            hdr.handle_event(event).unwrap_or_else(|e| panic!("{}", e));
+        });
+
+        // input
+        // -----
+        process_input(&mut window, delta_time, &mut camera);
+        hdr.process_input(&window, delta_time);
+        for (key, tone_mapping) in &modes {
+            if window.get_key(*key) == Action::Press && hdr.tone_mapping != *tone_mapping {
+                hdr.tone_mapping = *tone_mapping;
+                println!("tone mapping: {:?} (exposure {:.2})", hdr.tone_mapping, hdr.exposure);
+            }
+        }
+        // Space toggles auto exposure
+        if window.get_key(Key::Space) == Action::Press && !auto_exposure_key_pressed {
+            hdr.auto_exposure = !hdr.auto_exposure;
+            auto_exposure_key_pressed = true;
+            println!("auto exposure: {}", if hdr.auto_exposure { "on" } else { "off" });
+        }
+        if window.get_key(Key::Space) == Action::Release {
+            auto_exposure_key_pressed = false;
+        }
+
+        // render
+        // ------
+        unsafe {
+            // 1. render scene into floating point framebuffer
+            // -----------------------------------------------
+            hdr.begin();
+            gl::ClearColor(0.1, 0.1, 0.1, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+            let (width, height) = window.get_framebuffer_size();
+            let projection: Matrix4<f32> = perspective(Deg(camera.zoom), width as f32 / height as f32, 0.1, 100.0);
+            shader.use_program();
+            shader.set_mat4(c_str!("projection"), &projection);
+            shader.set_mat4(c_str!("view"), &camera.get_view_matrix());
+            gl::ActiveTexture(gl::TEXTURE0);
+            gl::BindTexture(gl::TEXTURE_2D, wood_texture);
+            // set lighting uniforms
+            for (i, (position, color)) in light_positions.iter().zip(&light_colors).enumerate() {
+                shader.set(&format!("lights[{}].Position", i), position);
+                shader.set(&format!("lights[{}].Color", i), color);
+            }
+            // render tunnel, twice LearnOpenGL's scale as CUBE_VERTICES only spans [-0.5, 0.5]
+            let model =
+                Matrix4::from_translation(vec3(0.0, 0.0, -25.0)) * Matrix4::from_nonuniform_scale(5.0, 5.0, 55.0);
+            shader.set_mat4(c_str!("model"), &model);
+            shader.set_bool(c_str!("inverseNormals"), true);
+            cube.draw();
+
+            // 2. now render floating point color buffer to 2D quad and tonemap HDR colors to default framebuffer's (clamped) color range
+            // -------------------------------------------------------------------------------------------------------------------------
+            hdr.end(width, height, delta_time);
+        }
+
+        // glfw: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
+        // -------------------------------------------------------------------------------
+        window.swap_buffers();
+        glfw.poll_events();
+    }
+}
@@ -136,6 +136,96 @@ impl TutorialGeometry {
         Self { vao, vbo, ebo: 0, elements: (vertices.len() / 5) as i32, primitive: gl::TRIANGLES }
     }
 
+    /// positions, normals and texture coordinates, as used by the lighting tutorials
+    pub unsafe fn new_xyznuv(vertices: Vec<f32>) -> Self {
+        let mut vao: u32 = 0;
+        let mut vbo: u32 = 0;
+        gl::GenVertexArrays(1, &mut vao);
+        gl::GenBuffers(1, &mut vbo);
+
+        gl::BindVertexArray(vao);
+        gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+        gl::BufferData(
+            gl::ARRAY_BUFFER,
+            (vertices.len() * mem::size_of::<GLfloat>()) as GLsizeiptr,
+            &vertices[0] as *const f32 as *const c_void,
+            gl::STATIC_DRAW,
+        );
+        let stride = 8 * mem::size_of::<GLfloat>() as GLsizei;
+        // position
+        gl::VertexAttribPointer(0, 3, gl::FLOAT, gl::FALSE, stride, ptr::null());
+        gl::EnableVertexAttribArray(0);
+        // normal
+        gl::VertexAttribPointer(1, 3, gl::FLOAT, gl::FALSE, stride, (3 * mem::size_of::<GLfloat>()) as *const c_void);
+        gl::EnableVertexAttribArray(1);
+        // texture coordinates
+        gl::VertexAttribPointer(2, 2, gl::FLOAT, gl::FALSE, stride, (6 * mem::size_of::<GLfloat>()) as *const c_void);
+        gl::EnableVertexAttribArray(2);
+
+        gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+        gl::BindVertexArray(0);
+
+        Self { vao, vbo, ebo: 0, elements: (vertices.len() / 8) as i32, primitive: gl::TRIANGLES }
+    }
+
+    /// 2D positions with texture coordinates, e.g. for screen-filling quads
+    pub unsafe fn new_xyuv(vertices: Vec<f32>) -> Self {
+        let mut vao: u32 = 0;
+        let mut vbo: u32 = 0;
+        gl::GenVertexArrays(1, &mut vao);
+        gl::GenBuffers(1, &mut vbo);
+
+        gl::BindVertexArray(vao);
+        gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+        gl::BufferData(
+            gl::ARRAY_BUFFER,
+            (vertices.len() * mem::size_of::<GLfloat>()) as GLsizeiptr,
+            &vertices[0] as *const f32 as *const c_void,
+            gl::STATIC_DRAW,
+        );
+        let stride = 4 * mem::size_of::<GLfloat>() as GLsizei;
+        // position
+        gl::VertexAttribPointer(0, 2, gl::FLOAT, gl::FALSE, stride, ptr::null());
+        gl::EnableVertexAttribArray(0);
+        // texture coordinates
+        gl::VertexAttribPointer(1, 2, gl::FLOAT, gl::FALSE, stride, (2 * mem::size_of::<GLfloat>()) as *const c_void);
+        gl::EnableVertexAttribArray(1);
+
+        gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+        gl::BindVertexArray(0);
+
+        Self { vao, vbo, ebo: 0, elements: (vertices.len() / 4) as i32, primitive: gl::TRIANGLES }
+    }
+
+    /// 2D positions with colors, e.g. for points expanded by a geometry shader
+    pub unsafe fn new_xyrgb(vertices: Vec<f32>) -> Self {
+        let mut vao: u32 = 0;
+        let mut vbo: u32 = 0;
+        gl::GenVertexArrays(1, &mut vao);
+        gl::GenBuffers(1, &mut vbo);
+
+        gl::BindVertexArray(vao);
+        gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+        gl::BufferData(
+            gl::ARRAY_BUFFER,
+            (vertices.len() * mem::size_of::<GLfloat>()) as GLsizeiptr,
+            &vertices[0] as *const f32 as *const c_void,
+            gl::STATIC_DRAW,
+        );
+        let stride = 5 * mem::size_of::<GLfloat>() as GLsizei;
+        // position
+        gl::VertexAttribPointer(0, 2, gl::FLOAT, gl::FALSE, stride, ptr::null());
+        gl::EnableVertexAttribArray(0);
+        // color
+        gl::VertexAttribPointer(1, 3, gl::FLOAT, gl::FALSE, stride, (2 * mem::size_of::<GLfloat>()) as *const c_void);
+        gl::EnableVertexAttribArray(1);
+
+        gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+        gl::BindVertexArray(0);
+
+        Self { vao, vbo, ebo: 0, elements: (vertices.len() / 5) as i32, primitive: gl::TRIANGLES }
+    }
+
     pub unsafe fn new_xyzrgbuv_indices(vertices: Vec<f32>, indices: Vec<i32>) -> Self {
         let mut vao: u32 = 0;
         let mut vbo: u32 = 0;
@@ -201,6 +291,16 @@ impl TutorialGeometry {
             gl::DrawArrays(self.primitive, 0, self.elements);
         }
     }
+
+    /// draws `count` instances, the per-instance attributes have to be set up on the VAO, see `bind`
+    pub unsafe fn draw_instanced(&self, count: i32) {
+        gl::BindVertexArray(self.vao);
+        if self.ebo != 0 {
+            gl::DrawElementsInstanced(self.primitive, self.elements, gl::UNSIGNED_INT, ptr::null(), count);
+        } else {
+            gl::DrawArraysInstanced(self.primitive, 0, self.elements, count);
+        }
+    }
 }
 
 impl Drop for TutorialGeometry {
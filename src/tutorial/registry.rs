@@ -0,0 +1,120 @@
+use crate::tutorial::*;
+
+/// A runnable tutorial. Every chapter module lists its tutorials in a
+/// `TUTORIALS` table, the `chapters!` macro in tutorial.rs collects them.
+pub struct Tutorial {
+    /// command line id, e.g. `1_2_1` for `_2_1_hello_triangle.rs` in chapter 1
+    pub id: &'static str,
+    pub chapter: u32,
+    pub title: &'static str,
+    pub main: fn(),
+}
+
+/// All tutorials of the enabled `chapter-N` features, in reading order
+pub fn tutorials() -> Vec<&'static Tutorial> {
+    chapter_tables().into_iter().flatten().collect()
+}
+
+pub fn chapter_tutorials(chapter: u32) -> Vec<&'static Tutorial> {
+    tutorials().into_iter().filter(|t| t.chapter == chapter).collect()
+}
+
+/// Looks up a tutorial by id (`1_2_1` or `1.2.1`) or by its exact title,
+/// falling back to fuzzy matching the query against the titles. Ties between
+/// fuzzy matches go to titles starting with the query, then to the shorter
+/// title. Returns all equally good matches, so more than one result means the
+/// query was ambiguous.
+pub fn find_tutorials(query: &str) -> Vec<&'static Tutorial> {
+    let id = query.replace('.', "_");
+    let all = tutorials();
+    if let Some(tutorial) = all.iter().find(|t| t.id == id) {
+        return vec![*tutorial];
+    }
+    let title = normalize_title(query);
+    if let Some(tutorial) = all.iter().find(|t| normalize_title(t.title) == title) {
+        return vec![*tutorial];
+    }
+
+    let ranked: Vec<_> = all
+        .into_iter()
+        .filter_map(|t| {
+            let prefix = normalize_title(t.title).starts_with(&title);
+            fuzzy_score(query, t.title).map(|score| ((score, !prefix, t.title.len()), t))
+        })
+        .collect();
+    match ranked.iter().map(|(rank, _)| *rank).min() {
+        Some(best) => ranked.into_iter().filter(|(rank, _)| *rank == best).map(|(_, t)| t).collect(),
+        None => Vec::new(),
+    }
+}
+
+/// lowercase with `_` as a space and single spaces between words, so that
+/// `hello_triangle` and `Hello  Triangle` compare equal
+fn normalize_title(title: &str) -> String {
+    title.to_lowercase().replace('_', " ").split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Case-insensitive fuzzy match: every character of `query` (ignoring spaces
+/// and underscores) has to appear in `text` in the same order. Returns the
+/// number of skipped characters between the first and last match, so 0 means
+/// `query` is a substring of `text`, or `None` if it doesn't match at all.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let query: Vec<char> = query.to_lowercase().chars().filter(|c| *c != ' ' && *c != '_').collect();
+    if query.is_empty() {
+        return None;
+    }
+
+    // try every start position of the first query character and keep the tightest match
+    (0..text.len())
+        .filter(|&start| text[start] == query[0])
+        .filter_map(|start| {
+            let mut pos = start;
+            let mut skipped = 0;
+            for c in &query[1..] {
+                let offset = text[pos + 1..].iter().position(|t| t == c)?;
+                skipped += text[pos + 1..pos + 1 + offset].iter().filter(|t| **t != ' ').count();
+                pos += offset + 1;
+            }
+            Some(skipped)
+        })
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found_ids(query: &str) -> Vec<&'static str> {
+        find_tutorials(query).iter().map(|t| t.id).collect()
+    }
+
+    #[test]
+    fn exact_title_beats_longer_titles_containing_it() {
+        assert_eq!(found_ids("hello triangle"), ["1_2_1"]);
+        assert_eq!(found_ids("Hello_Triangle"), ["1_2_1"]);
+        assert_eq!(found_ids("depth testing"), ["4_1_1"]);
+    }
+
+    #[test]
+    fn ties_go_to_the_title_starting_with_the_query() {
+        // a substring of "Depth Testing View" and "Coordinate Systems Depth" alike
+        assert_eq!(found_ids("depth"), ["4_1_1"]);
+    }
+
+    #[test]
+    fn ties_between_prefixes_go_to_the_shorter_title() {
+        assert_eq!(found_ids("hello tri"), ["1_2_1"]);
+    }
+
+    #[test]
+    fn ids_are_found_with_dots_or_underscores() {
+        assert_eq!(found_ids("1.2.1"), ["1_2_1"]);
+        assert_eq!(found_ids("4_1_1"), ["4_1_1"]);
+    }
+
+    #[test]
+    fn unknown_queries_find_nothing() {
+        assert!(found_ids("xyzzy").is_empty());
+    }
+}
@@ -0,0 +1,75 @@
+//! Runs the tutorials through the `gfx-rs` binary, each in its own process
+//! since glfw can only be initialized once per process.
+
+use std::collections::HashSet;
+use std::env;
+use std::process::Command;
+
+fn gfx_rs() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_gfx-rs"))
+}
+
+/// the ids printed by `gfx-rs list`
+fn tutorial_ids() -> Vec<String> {
+    let output = gfx_rs().arg("list").output().expect("Failed to run gfx-rs list");
+    assert!(output.status.success(), "gfx-rs list failed: {}", output.status);
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| line.starts_with(' '))
+        .filter_map(|line| line.split_whitespace().next())
+        .map(String::from)
+        .collect()
+}
+
+/// Whether the GL tests can run: `gfx-rs gl-info` has to get a headless GL
+/// context (see README), and `GFX_RS_SKIP_GL_TESTS` must not be set
+fn has_headless_gl() -> bool {
+    if env::var_os("GFX_RS_SKIP_GL_TESTS").is_some() {
+        eprintln!("skipped, GFX_RS_SKIP_GL_TESTS is set");
+        return false;
+    }
+    match gfx_rs().arg("gl-info").output() {
+        Ok(output) if output.status.success() => true,
+        _ => {
+            eprintln!("skipped, no headless GL context available");
+            false
+        }
+    }
+}
+
+#[test]
+fn lists_every_tutorial_once() {
+    let ids = tutorial_ids();
+    assert!(!ids.is_empty(), "no tutorials listed");
+    let mut seen = HashSet::new();
+    for id in &ids {
+        assert!(seen.insert(id), "tutorial {} listed twice", id);
+    }
+}
+
+#[test]
+fn every_tutorial_runs_headless() {
+    if !has_headless_gl() {
+        return;
+    }
+    let failed: Vec<String> = tutorial_ids()
+        .into_iter()
+        .filter(|id| {
+            let status = gfx_rs().args(&["--headless", "1", "run", id]).status().expect("Failed to run tutorial");
+            !status.success()
+        })
+        .collect();
+    assert!(failed.is_empty(), "tutorials failed: {}", failed.join(", "));
+}
+
+/// Compares every tutorial against its reference image in resources/golden,
+/// see README. Passes without comparing on a renderer the references weren't
+/// blessed on.
+#[test]
+fn every_tutorial_matches_its_golden_frame() {
+    if !has_headless_gl() {
+        return;
+    }
+    let status = gfx_rs().arg("golden").status().expect("Failed to run gfx-rs golden");
+    assert!(status.success(), "golden frames differ, see target/golden");
+}